@@ -31,16 +31,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .y_label_formatter(&|v| format!("{:.1}", v))
         .draw()?;
 
-    /*cc.define_series_label_area(
-        (720, 130),
-        (240, 100),
-        Into::<ShapeStyle>::into(&RGBColor(255, 255, 255).mix(0.7)).filled(),
-    )?;*/
-
     cc.draw_series(LineSeries::new(
         (0..12).map(|x| ((x - 6) as f32 / 2.0, ((x - 6) as f32 / 2.0).sin())),
         &RGBColor(255, 0, 0),
-    ))?;
+    ))?
+    .label("Sine")
+    .legend(LegendGlyph::line(&RGBColor(255, 0, 0)));
 
     cc.draw_series(LineSeries::new(
         (0..6800).map(|x| {
@@ -50,7 +46,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             )
         }),
         &RGBColor(0, 0, 255),
-    ))?;
+    ))?
+    .label("Cosine")
+    .legend(LegendGlyph::line(&RGBColor(0, 0, 255)));
 
     // It's possible to use a existing pointing element
     /*
@@ -71,7 +69,17 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 + Circle::new((0, 0), size, style)
                 + OwnedText::new(format!("{:?}", coord), (0, 15), &point_font);
         },
-    ))?;
+    ))?
+    .label("Sine samples")
+    .legend(LegendGlyph::marker(
+        Into::<ShapeStyle>::into(&RGBColor(255, 0, 0)).filled(),
+        5,
+    ));
+
+    cc.configure_series_labels()
+        .background_style(&RGBColor(255, 255, 255).mix(0.8))
+        .border_style(&RGBColor(0, 0, 0))
+        .draw()?;
 
     let drawing_areas = lower.split_evenly((1, 2));
 
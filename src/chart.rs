@@ -9,22 +9,102 @@ A chart can be build on top of any drawing area based on a pixel coordinate.
 use std::borrow::Borrow;
 use std::fmt::Debug;
 use std::marker::PhantomData;
-use std::ops::Range;
+use std::ops::{Deref, DerefMut, Range};
 
 use crate::coord::{
-    AsRangedCoord, CoordTranslate, MeshLine, Ranged, RangedCoord, ReverseCoordTranslate, Shift,
+    AsRangedCoord, Camera, CoordTranslate, MeshLine, PolarCoord, Ranged, RangedCoord,
+    RangedCoord3D, ReverseCoordTranslate, Shift,
 };
 
 use crate::drawing::backend::BackendCoord;
 use crate::drawing::backend::DrawingBackend;
 use crate::drawing::{DrawingArea, DrawingAreaErrorKind};
-use crate::element::{Drawable, Path, PointCollection, Rectangle};
-use crate::style::{FontDesc, Mixable, RGBColor, ShapeStyle, TextStyle};
+use crate::element::{Circle, Drawable, Path, PointCollection, Polygon, Rectangle};
+use crate::style::{
+    Color, FillRule, FontDesc, HAnchor, LineCap, LineJoin, Mixable, RGBColor, ShapeStyle,
+    TextLayout, TextStyle, VAnchor,
+};
+
+/// The space around the outer circle of a polar chart, which is reserved for the angle labels
+const POLAR_LABEL_SPACE: u32 = 30;
+
+/// The gap in pixels that is kept between two tick labels, labels which are closer than this
+/// are thinned out
+const LABEL_GAP: i32 = 5;
+
+/// The distance between the X axis and the top of the X tick labels
+const X_LABEL_TOP: i32 = 10;
+
+/// The gap between the tick labels and the axis titles
+const AXIS_DESC_GAP: i32 = 5;
+
+/// The corners of the box of a tick label in clockwise order, which is not axis-aligned when the
+/// label is rotated
+type LabelBox = [(f64, f64); 4];
+
+/// Check if two label boxes are closer than the label gap with the separating axis test
+fn label_boxes_intersect(a: &LabelBox, b: &LabelBox) -> bool {
+    let project = |corners: &LabelBox, (nx, ny): (f64, f64)| {
+        corners
+            .iter()
+            .fold((f64::MAX, f64::MIN), |(min, max), &(x, y)| {
+                (min.min(x * nx + y * ny), max.max(x * nx + y * ny))
+            })
+    };
+
+    for corners in [a, b].iter() {
+        for idx in 0..4 {
+            let (x0, y0) = corners[idx];
+            let (x1, y1) = corners[(idx + 1) % 4];
+            let len = (x1 - x0).hypot(y1 - y0);
+            if len == 0.0 {
+                continue;
+            }
+            let normal = ((y0 - y1) / len, (x1 - x0) / len);
+            let (a_min, a_max) = project(a, normal);
+            let (b_min, b_max) = project(b, normal);
+            if a_max + f64::from(LABEL_GAP) <= b_min || b_max + f64::from(LABEL_GAP) <= a_min {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Find the smallest step for the labels, such that no two labels would intersect if only every
+/// step-th label is drawn. The labels are expected to be ordered along their axis.
+fn label_step(boxes: &[LabelBox]) -> usize {
+    (1..boxes.len().max(1))
+        .find(|&step| {
+            boxes
+                .iter()
+                .step_by(step)
+                .zip(boxes.iter().step_by(step).skip(1))
+                .all(|(a, b)| !label_boxes_intersect(a, b))
+        })
+        .unwrap_or_else(|| boxes.len().max(1))
+}
+
+/// Get the layout of the X tick labels. Unrotated labels are centered below their ticks, while
+/// rotated labels end at their ticks, so that they hang down from them.
+fn x_label_layout(rotation: f64) -> TextLayout {
+    let sin = rotation.to_radians().sin();
+    let layout = if sin > 1e-6 {
+        TextLayout::new(HAnchor::Right, VAnchor::Middle)
+    } else if sin < -1e-6 {
+        TextLayout::new(HAnchor::Left, VAnchor::Middle)
+    } else {
+        TextLayout::new(HAnchor::Center, VAnchor::Top)
+    };
+    layout.rotate(rotation)
+}
 
 /// The helper object to create a chart context, which is used for the high-level figure drawing
 pub struct ChartBuilder<'a, DB: DrawingBackend> {
     x_label_size: u32,
     y_label_size: u32,
+    right_y_label_size: u32,
     root_area: &'a DrawingArea<DB, Shift>,
     title: Option<(String, TextStyle<'a>)>,
     margin: u32,
@@ -38,6 +118,7 @@ impl<'a, DB: DrawingBackend> ChartBuilder<'a, DB> {
         Self {
             x_label_size: 0,
             y_label_size: 0,
+            right_y_label_size: 0,
             root_area: root,
             title: None,
             margin: 0,
@@ -66,6 +147,15 @@ impl<'a, DB: DrawingBackend> ChartBuilder<'a, DB> {
         self
     }
 
+    /// Set the size of the Y label area on the right side of the chart, which is used by the
+    /// secondary coordinate if the chart has one
+    /// - `size`: The width of the right Y label area. If size is 0, the chart doesn't have the
+    ///   right Y label area
+    pub fn right_y_label_area_size(&mut self, size: u32) -> &mut Self {
+        self.right_y_label_size = size;
+        self
+    }
+
     /// Set the caption of the chart
     /// - `caption`: The caption of the chart
     /// - `style`: The text style
@@ -79,6 +169,96 @@ impl<'a, DB: DrawingBackend> ChartBuilder<'a, DB> {
         self
     }
 
+    /// Build the chart with a polar coordinate system, which takes the largest circle that fits
+    /// in the chart. The label area sizes are ignored, since the labels are drawn around the
+    /// circle.
+    /// - `radius_spec`: The specification of the radius
+    /// - Returns: A chart context
+    #[allow(clippy::type_complexity)]
+    pub fn build_polar<R: AsRangedCoord>(
+        &mut self,
+        radius_spec: R,
+    ) -> Result<ChartContext<DB, PolarCoord<R::CoordDescType>>, DrawingAreaErrorKind<DB::ErrorType>>
+    {
+        let mut drawing_area = DrawingArea::clone(self.root_area);
+
+        if self.margin > 0 {
+            let s = self.margin as i32;
+            drawing_area = drawing_area.margin(s, s, s, s);
+        }
+
+        if let Some((ref title, ref style)) = self.title {
+            drawing_area = drawing_area.titled(title, style.clone())?;
+        }
+
+        let (x_range, y_range) = drawing_area.get_pixel_range();
+        let center = (
+            (x_range.start + x_range.end) / 2,
+            (y_range.start + y_range.end) / 2,
+        );
+        let (w, h) = drawing_area.dim_in_pixel();
+        let radius = (w.min(h) / 2).saturating_sub(POLAR_LABEL_SPACE);
+
+        Ok(ChartContext {
+            x_label_area: None,
+            y_label_area: None,
+            right_y_label_area: None,
+            series_area: None,
+            series_anno: vec![],
+            drawing_area: drawing_area.apply_coord_spec(PolarCoord::new(
+                radius_spec,
+                center,
+                radius,
+            )),
+        })
+    }
+
+    /// Build the chart with a 3D cartesian coordinate system, which is projected with the
+    /// default camera. The label area sizes are ignored, since the labels are drawn next to the
+    /// edges of the axis planes.
+    /// - `x_spec`: The specification of X axis
+    /// - `y_spec`: The specification of Y axis
+    /// - `z_spec`: The specification of Z axis
+    /// - Returns: A chart context
+    #[allow(clippy::type_complexity)]
+    pub fn build_cartesian_3d<X: AsRangedCoord, Y: AsRangedCoord, Z: AsRangedCoord>(
+        &mut self,
+        x_spec: X,
+        y_spec: Y,
+        z_spec: Z,
+    ) -> Result<
+        ChartContext<DB, RangedCoord3D<X::CoordDescType, Y::CoordDescType, Z::CoordDescType>>,
+        DrawingAreaErrorKind<DB::ErrorType>,
+    > {
+        let mut drawing_area = DrawingArea::clone(self.root_area);
+
+        if self.margin > 0 {
+            let s = self.margin as i32;
+            drawing_area = drawing_area.margin(s, s, s, s);
+        }
+
+        if let Some((ref title, ref style)) = self.title {
+            drawing_area = drawing_area.titled(title, style.clone())?;
+        }
+
+        let pixel_range = drawing_area.get_pixel_range();
+
+        Ok(ChartContext {
+            x_label_area: None,
+            y_label_area: None,
+            right_y_label_area: None,
+            series_area: None,
+            series_anno: vec![],
+            drawing_area: drawing_area.apply_coord_spec(RangedCoord3D::new(
+                x_spec,
+                y_spec,
+                z_spec,
+                pixel_range,
+                Camera::default(),
+            )),
+        })
+    }
+
     /// Builder the chart with a ranged coordinate system. The function will returns a chart
     /// context, where data series can be rendered on.
     /// - `x_spec`: The specification of X axis
@@ -93,8 +273,71 @@ impl<'a, DB: DrawingBackend> ChartBuilder<'a, DB> {
         ChartContext<DB, RangedCoord<X::CoordDescType, Y::CoordDescType>>,
         DrawingAreaErrorKind<DB::ErrorType>,
     > {
+        self.build_ranged_coord(x_spec.into(), y_spec.into())
+    }
+
+    /// Build the chart with a ranged coordinate system and draw its mesh, where the X and Y
+    /// label areas are sized automatically. The mesh is configured by `configure`, just like the
+    /// mesh returned by `ChartContext::configure_mesh`. Its tick labels and axis titles are
+    /// measured with the configured formatters, label counts, rotation and styles, and the
+    /// label areas are made just large enough to fit them. The sizes set by
+    /// `x_label_area_size` and `y_label_area_size` are ignored, while the right Y label area
+    /// keeps the size set by `right_y_label_area_size`.
+    /// - `x_spec`: The specification of X axis
+    /// - `y_spec`: The specification of Y axis
+    /// - `configure`: The function that configures the mesh
+    /// - Returns: A chart context with the mesh drawn
+    ///
+    /// ```rust,no_run
+    /// use plotters::prelude::*;
+    /// let root: DrawingArea<_, _> = BitMapBackend::new("chart.png", (640, 480)).into();
+    /// let mut chart = ChartBuilder::on(&root)
+    ///     .build_ranged_with_mesh(0.0..1.0, 0.0..1e6, |mesh| {
+    ///         mesh.x_desc("Time").y_desc("Count").x_label_rotation(45.0);
+    ///     })
+    ///     .unwrap();
+    /// chart.draw_series(LineSeries::new(vec![(0.0, 0.0), (1.0, 1e6)], &Red)).unwrap();
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn build_ranged_with_mesh<X: AsRangedCoord, Y: AsRangedCoord, F>(
+        &mut self,
+        x_spec: X,
+        y_spec: Y,
+        configure: F,
+    ) -> Result<
+        ChartContext<DB, RangedCoord<X::CoordDescType, Y::CoordDescType>>,
+        DrawingAreaErrorKind<DB::ErrorType>,
+    >
+    where
+        F: FnOnce(&mut MeshStyle<'a, X::CoordDescType, Y::CoordDescType, DB>),
+        X::CoordDescType: 'a,
+        Y::CoordDescType: 'a,
+        <X::CoordDescType as Ranged>::ValueType: Debug + 'a,
+        <Y::CoordDescType as Ranged>::ValueType: Debug + 'a,
+    {
+        let x_spec: X::CoordDescType = x_spec.into();
+        let y_spec: Y::CoordDescType = y_spec.into();
+
+        let mut mesh = MeshStyle::new(None);
+        configure(&mut mesh);
+        let (x_label_size, y_label_size) = mesh.label_area_size(&x_spec, &y_spec);
+        self.x_label_size = x_label_size;
+        self.y_label_size = y_label_size;
+
+        let mut chart = self.build_ranged_coord(x_spec, y_spec)?;
+        mesh.draw_on(&mut chart)?;
+        Ok(chart)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn build_ranged_coord<X: Ranged, Y: Ranged>(
+        &mut self,
+        x_spec: X,
+        y_spec: Y,
+    ) -> Result<ChartContext<DB, RangedCoord<X, Y>>, DrawingAreaErrorKind<DB::ErrorType>> {
         let mut x_label_area = None;
         let mut y_label_area = None;
+        let mut right_y_label_area = None;
 
         let mut drawing_area = DrawingArea::clone(self.root_area);
 
@@ -126,13 +369,29 @@ impl<'a, DB: DrawingBackend> ChartBuilder<'a, DB> {
             }
         }
 
+        if self.right_y_label_size > 0 {
+            let (w, _) = drawing_area.dim_in_pixel();
+            let (left, right) =
+                drawing_area.split_horizentally(w as i32 - self.right_y_label_size as i32);
+            drawing_area = left;
+            right_y_label_area = Some(right);
+
+            if let Some(xl) = x_label_area {
+                let (w, _) = xl.dim_in_pixel();
+                let (left, _) = xl.split_horizentally(w as i32 - self.right_y_label_size as i32);
+                x_label_area = Some(left);
+            }
+        }
+
         let mut pixel_range = drawing_area.get_pixel_range();
         pixel_range.1 = pixel_range.1.end..pixel_range.1.start;
 
         Ok(ChartContext {
             x_label_area,
             y_label_area,
+            right_y_label_area,
             series_area: None,
+            series_anno: vec![],
             drawing_area: drawing_area.apply_coord_spec(RangedCoord::new(
                 x_spec,
                 y_spec,
@@ -148,10 +407,299 @@ impl<'a, DB: DrawingBackend> ChartBuilder<'a, DB> {
 pub struct ChartContext<DB: DrawingBackend, CT: CoordTranslate> {
     x_label_area: Option<DrawingArea<DB, Shift>>,
     y_label_area: Option<DrawingArea<DB, Shift>>,
+    right_y_label_area: Option<DrawingArea<DB, Shift>>,
     series_area: Option<DrawingArea<DB, Shift>>,
+    series_anno: Vec<SeriesAnno>,
     drawing_area: DrawingArea<DB, CT>,
 }
 
+#[derive(Clone, Copy, Debug)]
+enum GlyphKind {
+    Line,
+    Marker,
+    FilledBox,
+}
+
+/// The color of a legend glyph, which is copied from the style the glyph is created with
+#[derive(Clone, Copy, Debug)]
+struct GlyphColor((u8, u8, u8), f64);
+
+impl Color for GlyphColor {
+    fn rgb(&self) -> (u8, u8, u8) {
+        self.0
+    }
+
+    fn alpha(&self) -> f64 {
+        self.1
+    }
+}
+
+/// The glyph drawn in front of the series label in the legend
+#[derive(Clone, Debug)]
+pub struct LegendGlyph {
+    kind: GlyphKind,
+    /// The fields of the style the glyph is created with, which are owned by the glyph
+    color: GlyphColor,
+    filled: bool,
+    stroke_width: u32,
+    dash_pattern: Vec<u32>,
+    line_cap: LineCap,
+    line_join: LineJoin,
+    fill_rule: FillRule,
+    size: u32,
+}
+
+impl LegendGlyph {
+    fn new<'a, S: Into<ShapeStyle<'a>>>(kind: GlyphKind, style: S, size: u32) -> Self {
+        let style = style.into();
+        Self {
+            kind,
+            color: GlyphColor(style.color.rgb(), style.color.alpha()),
+            filled: style.filled,
+            stroke_width: style.stroke_width,
+            dash_pattern: style.dash_pattern.to_vec(),
+            line_cap: style.line_cap,
+            line_join: style.line_join,
+            fill_rule: style.fill_rule,
+            size,
+        }
+    }
+
+    /// A short line sample, which uses the stroke of the style, including its width and dash
+    /// pattern
+    pub fn line<'a, S: Into<ShapeStyle<'a>>>(style: S) -> Self {
+        Self::new(GlyphKind::Line, style, 0)
+    }
+
+    /// A circle marker
+    /// - `style`: The style of the marker, the marker is filled if the style is filled
+    /// - `size`: The radius of the marker
+    pub fn marker<'a, S: Into<ShapeStyle<'a>>>(style: S, size: u32) -> Self {
+        Self::new(GlyphKind::Marker, style, size)
+    }
+
+    /// A box filled with the color of the style
+    pub fn filled_box<'a, S: Into<ShapeStyle<'a>>>(style: S) -> Self {
+        Self::new(GlyphKind::FilledBox, style, 0)
+    }
+
+    /// Draw the glyph on the legend, centered at the given position
+    fn draw<DB: DrawingBackend>(
+        &self,
+        area: &DrawingArea<DB, Shift>,
+        (x, y): BackendCoord,
+    ) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+        let style = ShapeStyle {
+            color: &self.color,
+            filled: self.filled,
+            stroke_width: self.stroke_width,
+            dash_pattern: &self.dash_pattern,
+            line_cap: self.line_cap,
+            line_join: self.line_join,
+            fill_rule: self.fill_rule,
+        };
+        let half = LEGEND_GLYPH_WIDTH as i32 / 2;
+        match self.kind {
+            GlyphKind::Line => area.draw(&Path::new(vec![(x - half, y), (x + half, y)], style)),
+            GlyphKind::Marker => area.draw(&Circle::new((x, y), self.size, style)),
+            GlyphKind::FilledBox => area.draw(&Rectangle::new(
+                [(x - half, y - half / 2), (x + half, y + half / 2)],
+                style.filled(),
+            )),
+        }
+    }
+}
+
+/// The annotation of a drawn series, which is used to render the legend
+#[derive(Default)]
+pub struct SeriesAnno {
+    label: Option<String>,
+    glyph: Option<LegendGlyph>,
+}
+
+impl SeriesAnno {
+    /// Set the label of the series, only the labeled series are shown in the legend
+    /// - `label`: The label text
+    pub fn label<L: Into<String>>(&mut self, label: L) -> &mut Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set the glyph drawn in front of the label in the legend
+    /// - `glyph`: The legend glyph
+    pub fn legend(&mut self, glyph: LegendGlyph) -> &mut Self {
+        self.glyph = Some(glyph);
+        self
+    }
+}
+
+/// The width of the glyph column of the legend
+const LEGEND_GLYPH_WIDTH: u32 = 20;
+
+/// The position of the legend
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SeriesLabelPosition {
+    UpperLeft,
+    UpperMiddle,
+    UpperRight,
+    MiddleLeft,
+    MiddleMiddle,
+    MiddleRight,
+    LowerLeft,
+    LowerMiddle,
+    LowerRight,
+    /// The upper left corner of the legend, relative to the series label area
+    Coordinate(i32, i32),
+}
+
+impl SeriesLabelPosition {
+    fn layout(self, (w, h): (u32, u32), (area_w, area_h): (u32, u32), margin: i32) -> BackendCoord {
+        use SeriesLabelPosition::*;
+        let (w, h, area_w, area_h) = (w as i32, h as i32, area_w as i32, area_h as i32);
+        let x = match self {
+            UpperLeft | MiddleLeft | LowerLeft => margin,
+            UpperMiddle | MiddleMiddle | LowerMiddle => (area_w - w) / 2,
+            UpperRight | MiddleRight | LowerRight => area_w - w - margin,
+            Coordinate(x, _) => x,
+        };
+        let y = match self {
+            UpperLeft | UpperMiddle | UpperRight => margin,
+            MiddleLeft | MiddleMiddle | MiddleRight => (area_h - h) / 2,
+            LowerLeft | LowerMiddle | LowerRight => area_h - h - margin,
+            Coordinate(_, y) => y,
+        };
+        (x, y)
+    }
+}
+
+/// The struct that is used for tracking the configuration of the legend
+pub struct SeriesLabelStyle<'a, DB: DrawingBackend, CT: CoordTranslate> {
+    position: SeriesLabelPosition,
+    margin: u32,
+    border_style: Option<ShapeStyle<'a>>,
+    background_style: Option<ShapeStyle<'a>>,
+    label_style: Option<TextStyle<'a>>,
+    target: Option<&'a mut ChartContext<DB, CT>>,
+}
+
+impl<'a, DB: DrawingBackend, CT: CoordTranslate> SeriesLabelStyle<'a, DB, CT> {
+    /// Set the position of the legend in the series label area
+    /// - `pos`: The position of the legend
+    pub fn position(&mut self, pos: SeriesLabelPosition) -> &mut Self {
+        self.position = pos;
+        self
+    }
+
+    /// Set the distance between the legend and the border of the series label area
+    /// - `value`: The margin in pixels
+    pub fn margin(&mut self, value: u32) -> &mut Self {
+        self.margin = value;
+        self
+    }
+
+    /// Set the style of the border of the legend, the legend doesn't have a border by default
+    /// - `style`: The border style
+    pub fn border_style<T: Into<ShapeStyle<'a>>>(&mut self, style: T) -> &mut Self {
+        self.border_style = Some(style.into());
+        self
+    }
+
+    /// Set the background style of the legend, the legend doesn't have a background by default
+    /// - `style`: The background style
+    pub fn background_style<T: Into<ShapeStyle<'a>>>(&mut self, style: T) -> &mut Self {
+        self.background_style = Some(style.into());
+        self
+    }
+
+    /// Set the style of the label text
+    /// - `style`: The text style that would be applied to the labels
+    pub fn label_style<T: Into<TextStyle<'a>>>(&mut self, style: T) -> &mut Self {
+        self.label_style = Some(style.into());
+        self
+    }
+
+    /// Draw the legend. The legend is laid out in the series label area if it's defined by
+    /// `ChartContext::define_series_label_area`, otherwise in the plotting area.
+    pub fn draw(&mut self) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+        let target = self.target.take().unwrap();
+
+        let default_label_font = FontDesc::new("Arial", 12.0);
+        let label_style = match self.label_style {
+            Some(ref style) => style.clone(),
+            None => (&default_label_font).into(),
+        };
+
+        let entries: Vec<_> = target
+            .series_anno
+            .iter()
+            .filter_map(|anno| {
+                let label = anno.label.as_ref()?;
+                let size = label_style.font.box_size(label).unwrap_or((0, 0));
+                Some((label, anno.glyph.as_ref(), size))
+            })
+            .collect();
+
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let padding = 5;
+        let row_height = entries
+            .iter()
+            .map(|(_, _, (_, h))| *h)
+            .max()
+            .unwrap_or(0)
+            .max(LEGEND_GLYPH_WIDTH / 2)
+            + padding;
+        let text_width = entries.iter().map(|(_, _, (w, _))| *w).max().unwrap_or(0);
+        let size = (
+            padding * 3 + LEGEND_GLYPH_WIDTH + text_width,
+            padding + row_height * entries.len() as u32,
+        );
+
+        let container = match target.series_area {
+            Some(ref area) => area.clone(),
+            None => target.drawing_area.strip_coord_spec(),
+        };
+        let (x0, y0) = self
+            .position
+            .layout(size, container.dim_in_pixel(), self.margin as i32);
+        let (x1, y1) = (x0 + size.0 as i32, y0 + size.1 as i32);
+
+        if let Some(ref style) = self.background_style {
+            container.draw(&Rectangle::new([(x0, y0), (x1, y1)], style.filled()))?;
+        }
+        if let Some(ref style) = self.border_style {
+            container.draw(&Rectangle::new([(x0, y0), (x1, y1)], style.clone()))?;
+        }
+
+        for (idx, (label, glyph, _)) in entries.into_iter().enumerate() {
+            let top = y0 + (padding + row_height * idx as u32) as i32;
+            let middle = top + (row_height - padding) as i32 / 2;
+            if let Some(glyph) = glyph {
+                let x = x0 + (padding + LEGEND_GLYPH_WIDTH / 2) as i32;
+                glyph.draw(&container, (x, middle))?;
+            }
+            let x = x0 + (padding * 2 + LEGEND_GLYPH_WIDTH) as i32;
+            container.draw_text(
+                label,
+                &label_style.layout(TextLayout::new(HAnchor::Left, VAnchor::Middle)),
+                (x, middle),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The chart context with a secondary coordinate system, which shares the X axis with the
+/// primary one and uses the right Y label area for its labels.
+/// All the methods of the primary chart context are available through `Deref`.
+pub struct DualCoordChartContext<DB: DrawingBackend, CT1: CoordTranslate, CT2: CoordTranslate> {
+    primary: ChartContext<DB, CT1>,
+    secondary: ChartContext<DB, CT2>,
+}
+
 /// The struct that is used for tracking the configuration of a mesh of any chart
 pub struct MeshStyle<'a, X: Ranged, Y: Ranged, DB>
 where
@@ -162,6 +710,7 @@ where
     draw_x_axis: bool,
     draw_y_axis: bool,
     x_label_offset: i32,
+    x_label_rotation: f64,
     n_x_labels: usize,
     n_y_labels: usize,
     line_style_1: Option<ShapeStyle<'a>>,
@@ -170,10 +719,48 @@ where
     label_style: Option<TextStyle<'a>>,
     format_x: &'a dyn Fn(&X::ValueType) -> String,
     format_y: &'a dyn Fn(&Y::ValueType) -> String,
+    x_desc: Option<String>,
+    y_desc: Option<String>,
+    axis_desc_style: Option<TextStyle<'a>>,
     target: Option<&'a mut ChartContext<DB, RangedCoord<X, Y>>>,
     _pahtom_data: PhantomData<(X, Y)>,
 }
 
+impl<'a, X, Y, DB> MeshStyle<'a, X, Y, DB>
+where
+    X: Ranged,
+    Y: Ranged,
+    X::ValueType: Debug,
+    Y::ValueType: Debug,
+    DB: DrawingBackend,
+{
+    /// Create a mesh configuration with the default settings
+    /// - `target`: The chart the mesh is drawn on by `draw`
+    fn new(target: Option<&'a mut ChartContext<DB, RangedCoord<X, Y>>>) -> Self {
+        MeshStyle {
+            axis_style: None,
+            x_label_offset: 0,
+            x_label_rotation: 0.0,
+            draw_x_mesh: true,
+            draw_y_mesh: true,
+            draw_x_axis: true,
+            draw_y_axis: true,
+            n_x_labels: 10,
+            n_y_labels: 10,
+            line_style_1: None,
+            line_style_2: None,
+            label_style: None,
+            format_x: &|x| format!("{:?}", x),
+            format_y: &|y| format!("{:?}", y),
+            x_desc: None,
+            y_desc: None,
+            axis_desc_style: None,
+            target,
+            _pahtom_data: PhantomData,
+        }
+    }
+}
+
 impl<'a, X, Y, DB> MeshStyle<'a, X, Y, DB>
 where
     X: Ranged,
@@ -188,6 +775,14 @@ where
         self
     }
 
+    /// Rotate the X labels. A rotated label ends at its tick, which keeps long labels readable
+    /// when they don't fit side by side. Labels that would still overlap are thinned out.
+    /// - `degrees`: The rotation angle in degrees, counter-clockwise, e.g. 45 or 90
+    pub fn x_label_rotation(&mut self, degrees: f64) -> &mut Self {
+        self.x_label_rotation = degrees;
+        self
+    }
+
     /// Disable the mesh for the x axis.
     pub fn disable_x_mesh(&mut self) -> &mut Self {
         self.draw_x_mesh = false;
@@ -267,12 +862,89 @@ where
         self
     }
 
+    /// Set the title of the X axis, which is drawn centered below the X tick labels. The X label
+    /// area should be large enough for both the labels and the title.
+    /// - `desc`: The title of the X axis
+    pub fn x_desc<T: Into<String>>(&mut self, desc: T) -> &mut Self {
+        self.x_desc = Some(desc.into());
+        self
+    }
+
+    /// Set the title of the Y axis, which is drawn rotated by 90 degrees on the outer side of
+    /// the Y tick labels. The Y label area should be large enough for both the labels and the
+    /// title.
+    /// - `desc`: The title of the Y axis
+    pub fn y_desc<T: Into<String>>(&mut self, desc: T) -> &mut Self {
+        self.y_desc = Some(desc.into());
+        self
+    }
+
+    /// Set the style of the axis titles
+    /// - `style`: The text style that would be applied to the axis titles
+    pub fn axis_desc_style<T: Into<TextStyle<'a>>>(&mut self, style: T) -> &mut Self {
+        self.axis_desc_style = Some(style.into());
+        self
+    }
+
     /// Draw the configured mesh on the target plot
     pub fn draw(&mut self) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
-        let mut target = None;
-        std::mem::swap(&mut target, &mut self.target);
-        let target = target.unwrap();
+        let target = self.target.take().unwrap();
+        self.draw_on(target)
+    }
 
+    /// Measure the tick labels and axis titles of the mesh, which are drawn with the given
+    /// coordinate specifications, the same way as `draw` lays them out
+    /// - Returns: The height of the X label area and the width of the Y label area
+    fn label_area_size(&self, x_spec: &X, y_spec: &Y) -> (u32, u32) {
+        let default_label_font = FontDesc::new("Arial", 12.0);
+        let label_style = match self.label_style {
+            Some(ref style) => style.clone(),
+            None => (&default_label_font).into(),
+        };
+        let axis_desc_style = match self.axis_desc_style {
+            Some(ref style) => style.clone(),
+            None => label_style.clone(),
+        };
+        let desc_height = |desc: &Option<String>| match desc {
+            Some(desc) => {
+                let (_, h) = axis_desc_style.font.box_size(desc).unwrap_or((0, 0));
+                AXIS_DESC_GAP + h as i32
+            }
+            None => 0,
+        };
+
+        // The labels are laid out at the origin, so the extent is the distance from the axis
+        let extent = |layout: TextLayout, text: String, distance: &dyn Fn(&(f64, f64)) -> f64| {
+            let outline = layout.outline(label_style.font, &text, (0, 0));
+            let outline = outline.unwrap_or([(0.0, 0.0); 4]);
+            outline.iter().map(distance).fold(0.0, f64::max).ceil() as i32
+        };
+        let x_layout = x_label_layout(self.x_label_rotation);
+        let x_extent = x_spec
+            .key_points(self.n_x_labels)
+            .iter()
+            .map(|x| extent(x_layout, (self.format_x)(x), &|c| c.1))
+            .max()
+            .unwrap_or(0);
+        let y_layout = TextLayout::new(HAnchor::Right, VAnchor::Middle);
+        let y_extent = y_spec
+            .key_points(self.n_y_labels)
+            .iter()
+            .map(|y| extent(y_layout, (self.format_y)(y), &|c| -c.0))
+            .max()
+            .unwrap_or(0);
+
+        // Keep 5 pixels of padding on the outer side of the label area
+        let x_size = X_LABEL_TOP + x_extent + desc_height(&self.x_desc) + 5;
+        let y_size = 10 + y_extent + desc_height(&self.y_desc) + 5;
+        (x_size as u32, y_size as u32)
+    }
+
+    /// Draw the configured mesh on the given chart
+    fn draw_on(
+        &self,
+        target: &mut ChartContext<DB, RangedCoord<X, Y>>,
+    ) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
         let default_mesh_color_1 = RGBColor(0, 0, 0).mix(0.2);
         let default_mesh_color_2 = RGBColor(0, 0, 0).mix(0.1);
         let default_axis_color = RGBColor(0, 0, 0);
@@ -303,12 +975,13 @@ where
             self.draw_x_mesh,
             self.draw_y_mesh,
             self.x_label_offset,
+            self.x_label_rotation,
             false,
             false,
             &axis_style,
         )?;
 
-        target.draw_mesh(
+        let extents = target.draw_mesh(
             (self.n_y_labels, self.n_x_labels),
             &mesh_style_1,
             &label_style,
@@ -319,44 +992,462 @@ where
             self.draw_x_mesh,
             self.draw_y_mesh,
             self.x_label_offset,
+            self.x_label_rotation,
             self.draw_x_axis,
             self.draw_y_axis,
             &axis_style,
+        )?;
+
+        let axis_desc_style = match self.axis_desc_style {
+            Some(ref style) => style.clone(),
+            None => label_style,
+        };
+
+        target.draw_axis_desc(
+            self.x_desc.as_deref(),
+            self.y_desc.as_deref(),
+            &axis_desc_style,
+            extents,
         )
     }
 }
 
-impl<
-        DB: DrawingBackend,
-        XT: Debug,
-        YT: Debug,
-        X: Ranged<ValueType = XT>,
-        Y: Ranged<ValueType = YT>,
-    > ChartContext<DB, RangedCoord<X, Y>>
-{
-    /// Initialize a mesh configuration object and mesh drawing can be finalized by calling
-    /// the function `MeshStyle::draw`
-    pub fn configure_mesh(&mut self) -> MeshStyle<X, Y, DB> {
-        MeshStyle {
-            axis_style: None,
-            x_label_offset: 0,
-            draw_x_mesh: true,
-            draw_y_mesh: true,
-            draw_x_axis: true,
-            draw_y_axis: true,
-            n_x_labels: 10,
-            n_y_labels: 10,
-            line_style_1: None,
-            line_style_2: None,
+/// The struct that is used for tracking the mesh configuration of a polar chart
+pub struct PolarMeshStyle<'a, R: Ranged, DB: DrawingBackend> {
+    draw_rings: bool,
+    draw_spokes: bool,
+    draw_labels: bool,
+    n_rings: usize,
+    n_spokes: usize,
+    line_style: Option<ShapeStyle<'a>>,
+    label_style: Option<TextStyle<'a>>,
+    format_radius: &'a dyn Fn(&R::ValueType) -> String,
+    format_angle: &'a dyn Fn(f64) -> String,
+    target: Option<&'a mut ChartContext<DB, PolarCoord<R>>>,
+}
+
+impl<'a, R: Ranged, DB: DrawingBackend> PolarMeshStyle<'a, R, DB> {
+    /// Set how many rings the mesh has at most
+    /// - `value`: The maximum desired number of rings, which are labeled with the radius
+    pub fn rings(&mut self, value: usize) -> &mut Self {
+        self.n_rings = value;
+        self
+    }
+
+    /// Set how many spokes the mesh has, the spokes evenly divide the full circle
+    /// - `value`: The number of spokes, which are labeled with the angle
+    pub fn spokes(&mut self, value: usize) -> &mut Self {
+        self.n_spokes = value;
+        self
+    }
+
+    /// Disable drawing the rings
+    pub fn disable_rings(&mut self) -> &mut Self {
+        self.draw_rings = false;
+        self
+    }
+
+    /// Disable drawing the spokes
+    pub fn disable_spokes(&mut self) -> &mut Self {
+        self.draw_spokes = false;
+        self
+    }
+
+    /// Disable drawing the labels
+    pub fn disable_labels(&mut self) -> &mut Self {
+        self.draw_labels = false;
+        self
+    }
+
+    /// Set the style of the rings and spokes
+    /// - `style`: The style of the mesh lines
+    pub fn line_style<T: Into<ShapeStyle<'a>>>(&mut self, style: T) -> &mut Self {
+        self.line_style = Some(style.into());
+        self
+    }
+
+    /// Set the style of the label text
+    /// - `style`: The text style that would be applied to the labels
+    pub fn label_style<T: Into<TextStyle<'a>>>(&mut self, style: T) -> &mut Self {
+        self.label_style = Some(style.into());
+        self
+    }
+
+    /// Set the formatter function for the radius labels
+    /// - `fmt`: The formatter function
+    pub fn radius_label_formatter(
+        &mut self,
+        fmt: &'a dyn Fn(&R::ValueType) -> String,
+    ) -> &mut Self {
+        self.format_radius = fmt;
+        self
+    }
+
+    /// Set the formatter function for the angle labels
+    /// - `fmt`: The formatter function, which takes the angle in degrees
+    pub fn angle_label_formatter(&mut self, fmt: &'a dyn Fn(f64) -> String) -> &mut Self {
+        self.format_angle = fmt;
+        self
+    }
+
+    /// Draw the configured mesh on the target plot
+    pub fn draw(&mut self) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+        let target = self.target.take().unwrap();
+
+        let default_mesh_color = RGBColor(0, 0, 0).mix(0.2);
+        let default_label_font = FontDesc::new("Arial", 12.0);
+
+        let line_style = self
+            .line_style
+            .clone()
+            .unwrap_or_else(|| (&default_mesh_color).into());
+        let label_style = match self.label_style {
+            Some(ref style) => style.clone(),
+            None => (&default_label_font).into(),
+        };
+
+        let coord = target.drawing_area.as_coord_spec();
+        let area = target.drawing_area.strip_coord_spec();
+        let (x0, y0) = area.get_base_pixel();
+        let to_area = |(x, y): BackendCoord| (x - x0, y - y0);
+
+        let center = to_area(coord.get_center());
+        let outer = coord.get_back_radius() as i32;
+
+        if self.draw_rings {
+            for r in coord.radius_key_points(self.n_rings) {
+                let d = coord.map_radius(&r);
+                if d > 0 {
+                    area.draw(&Circle::new(center, d as u32, line_style.clone()))?;
+                }
+                if self.draw_labels {
+                    let (x, y) = to_area(coord.polar_to_backend(0.0, d));
+                    area.draw_text(
+                        &(self.format_radius)(&r),
+                        &label_style.layout(TextLayout::new(HAnchor::Left, VAnchor::Bottom)),
+                        (x + 3, y),
+                    )?;
+                }
+            }
+            area.draw(&Circle::new(center, outer as u32, line_style.clone()))?;
+        }
+
+        let centered = label_style.layout(TextLayout::new(HAnchor::Center, VAnchor::Middle));
+        for idx in 0..self.n_spokes {
+            let angle = 360.0 * idx as f64 / self.n_spokes as f64;
+            if self.draw_spokes {
+                let end = to_area(coord.polar_to_backend(angle, outer));
+                area.draw(&Path::new(vec![center, end], line_style.clone()))?;
+            }
+            if self.draw_labels {
+                let text = (self.format_angle)(angle);
+                let (w, h) = label_style.font.box_size(&text).unwrap_or((0, 0));
+                let (w, h) = (f64::from(w) / 2.0, f64::from(h) / 2.0);
+                // Put the label box just outside of the outer circle, in the spoke direction
+                let (sin, cos) = angle.to_radians().sin_cos();
+                let (x, y) = to_area(coord.polar_to_backend(angle, outer + 5));
+                let x = (f64::from(x) + w * sin).round() as i32;
+                let y = (f64::from(y) - h * cos).round() as i32;
+                area.draw_text(&text, &centered, (x, y))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<DB: DrawingBackend, RT: Debug, R: Ranged<ValueType = RT>> ChartContext<DB, PolarCoord<R>> {
+    /// Initialize a polar mesh configuration object, which draws the rings and the spokes of the
+    /// chart. The mesh drawing can be finalized by calling the function `PolarMeshStyle::draw`
+    pub fn configure_mesh(&mut self) -> PolarMeshStyle<'_, R, DB> {
+        PolarMeshStyle {
+            draw_rings: true,
+            draw_spokes: true,
+            draw_labels: true,
+            n_rings: 5,
+            n_spokes: 8,
+            line_style: None,
+            label_style: None,
+            format_radius: &|r| format!("{:?}", r),
+            format_angle: &|a| format!("{}°", a),
+            target: Some(self),
+        }
+    }
+}
+
+/// The struct that is used for tracking the axis configuration of a 3D chart
+pub struct Mesh3DStyle<'a, X: Ranged, Y: Ranged, Z: Ranged, DB: DrawingBackend> {
+    draw_labels: bool,
+    n_x_labels: usize,
+    n_y_labels: usize,
+    n_z_labels: usize,
+    line_style: Option<ShapeStyle<'a>>,
+    axis_style: Option<ShapeStyle<'a>>,
+    panel_style: Option<ShapeStyle<'a>>,
+    label_style: Option<TextStyle<'a>>,
+    format_x: &'a dyn Fn(&X::ValueType) -> String,
+    format_y: &'a dyn Fn(&Y::ValueType) -> String,
+    format_z: &'a dyn Fn(&Z::ValueType) -> String,
+    target: Option<&'a mut ChartContext<DB, RangedCoord3D<X, Y, Z>>>,
+}
+
+impl<'a, X: Ranged, Y: Ranged, Z: Ranged, DB: DrawingBackend> Mesh3DStyle<'a, X, Y, Z, DB> {
+    /// Set how many labels for the X axis at most
+    /// - `value`: The maximum desired number of labels in the X axis
+    pub fn x_labels(&mut self, value: usize) -> &mut Self {
+        self.n_x_labels = value;
+        self
+    }
+
+    /// Set how many labels for the Y axis at most
+    /// - `value`: The maximum desired number of labels in the Y axis
+    pub fn y_labels(&mut self, value: usize) -> &mut Self {
+        self.n_y_labels = value;
+        self
+    }
+
+    /// Set how many labels for the Z axis at most
+    /// - `value`: The maximum desired number of labels in the Z axis
+    pub fn z_labels(&mut self, value: usize) -> &mut Self {
+        self.n_z_labels = value;
+        self
+    }
+
+    /// Disable drawing the labels
+    pub fn disable_labels(&mut self) -> &mut Self {
+        self.draw_labels = false;
+        self
+    }
+
+    /// Set the style of the mesh lines on the axis planes
+    /// - `style`: The style of the mesh lines
+    pub fn line_style<T: Into<ShapeStyle<'a>>>(&mut self, style: T) -> &mut Self {
+        self.line_style = Some(style.into());
+        self
+    }
+
+    /// Set the style of the borders of the axis planes
+    /// - `style`: The style of the axis plane borders
+    pub fn axis_style<T: Into<ShapeStyle<'a>>>(&mut self, style: T) -> &mut Self {
+        self.axis_style = Some(style.into());
+        self
+    }
+
+    /// Set the style used to fill the axis planes, the planes are not filled by default
+    /// - `style`: The fill style of the axis planes
+    pub fn panel_style<T: Into<ShapeStyle<'a>>>(&mut self, style: T) -> &mut Self {
+        self.panel_style = Some(style.into());
+        self
+    }
+
+    /// Set the style of the label text
+    /// - `style`: The text style that would be applied to the labels
+    pub fn label_style<T: Into<TextStyle<'a>>>(&mut self, style: T) -> &mut Self {
+        self.label_style = Some(style.into());
+        self
+    }
+
+    /// Set the formatter function for the X label text
+    /// - `fmt`: The formatter function
+    pub fn x_label_formatter(&mut self, fmt: &'a dyn Fn(&X::ValueType) -> String) -> &mut Self {
+        self.format_x = fmt;
+        self
+    }
+
+    /// Set the formatter function for the Y label text
+    /// - `fmt`: The formatter function
+    pub fn y_label_formatter(&mut self, fmt: &'a dyn Fn(&Y::ValueType) -> String) -> &mut Self {
+        self.format_y = fmt;
+        self
+    }
+
+    /// Set the formatter function for the Z label text
+    /// - `fmt`: The formatter function
+    pub fn z_label_formatter(&mut self, fmt: &'a dyn Fn(&Z::ValueType) -> String) -> &mut Self {
+        self.format_z = fmt;
+        self
+    }
+
+    /// Draw the configured axis planes on the target plot
+    pub fn draw(&mut self) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+        let target = self.target.take().unwrap();
+
+        let default_mesh_color = RGBColor(0, 0, 0).mix(0.2);
+        let default_axis_color = RGBColor(0, 0, 0);
+        let default_label_font = FontDesc::new("Arial", 12.0);
+
+        let line_style = self
+            .line_style
+            .clone()
+            .unwrap_or_else(|| (&default_mesh_color).into());
+        let axis_style = self
+            .axis_style
+            .clone()
+            .unwrap_or_else(|| (&default_axis_color).into());
+        let label_style = match self.label_style {
+            Some(ref style) => style.clone(),
+            None => (&default_label_font).into(),
+        };
+
+        let coord = target.drawing_area.as_coord_spec();
+        let area = target.drawing_area.strip_coord_spec();
+        let (x0, y0) = area.get_base_pixel();
+        let project = |p: (f64, f64, f64)| {
+            let (x, y) = coord.project(p);
+            (x - x0, y - y0)
+        };
+
+        // For each axis, the side of the unit cube which is farther away from the viewer
+        let back_side = |axis: usize| {
+            let mut unit = [0.0; 3];
+            unit[axis] = 1.0;
+            if coord.rotate((unit[0], unit[1], unit[2])).2 > 0.0 {
+                0.5
+            } else {
+                -0.5
+            }
+        };
+        let back = [back_side(0), back_side(1), back_side(2)];
+
+        let (xkp, ykp, zkp) = coord.key_points((self.n_x_labels, self.n_y_labels, self.n_z_labels));
+        let x_pos: Vec<_> = xkp.iter().map(|x| coord.normalize_x(x)).collect();
+        let y_pos: Vec<_> = ykp.iter().map(|y| coord.normalize_y(y)).collect();
+        let z_pos: Vec<_> = zkp.iter().map(|z| coord.normalize_z(z)).collect();
+        let key_pos = [&x_pos, &y_pos, &z_pos];
+
+        // Build a point in the unit cube from the value on each axis
+        let point = |values: [(usize, f64); 3]| {
+            let mut p = [0.0; 3];
+            for &(axis, value) in values.iter() {
+                p[axis] = value;
+            }
+            (p[0], p[1], p[2])
+        };
+
+        // Each plane is perpendicular to one axis and spans the other two
+        for &(normal, a, b) in [(2, 0, 1), (0, 2, 1), (1, 0, 2)].iter() {
+            let corners: Vec<_> = [(-0.5, -0.5), (0.5, -0.5), (0.5, 0.5), (-0.5, 0.5)]
+                .iter()
+                .map(|&(u, v)| project(point([(normal, back[normal]), (a, u), (b, v)])))
+                .collect();
+
+            if let Some(ref style) = self.panel_style {
+                area.draw(&Polygon::new(corners.clone(), style.filled()))?;
+            }
+
+            for &(axis, other) in [(a, b), (b, a)].iter() {
+                for &u in key_pos[axis].iter() {
+                    let from = point([(normal, back[normal]), (axis, u), (other, -0.5)]);
+                    let to = point([(normal, back[normal]), (axis, u), (other, 0.5)]);
+                    area.draw(&Path::new(
+                        vec![project(from), project(to)],
+                        line_style.clone(),
+                    ))?;
+                }
+            }
+
+            let mut border = corners;
+            border.push(border[0]);
+            area.draw(&Path::new(border, axis_style.clone()))?;
+        }
+
+        if !self.draw_labels {
+            return Ok(());
+        }
+
+        let center = project((0.0, 0.0, 0.0));
+        let centered = label_style.layout(TextLayout::new(HAnchor::Center, VAnchor::Middle));
+        let draw_label = |text: String, p: (f64, f64, f64)| {
+            let (x, y) = project(p);
+            let (w, h) = label_style.font.box_size(&text).unwrap_or((0, 0));
+            // Move the label away from the center of the cube
+            let (dx, dy) = (f64::from(x - center.0), f64::from(y - center.1));
+            let len = dx.hypot(dy).max(1.0);
+            let (w, h) = (f64::from(w), f64::from(h));
+            let x = f64::from(x) + dx / len * (w / 2.0 + 5.0);
+            let y = f64::from(y) + dy / len * (h / 2.0 + 5.0);
+            area.draw_text(&text, &centered, (x.round() as i32, y.round() as i32))
+        };
+
+        // The X and Z labels are on the front edges of the bottom plane, and the Y labels are on
+        // the free vertical edge of the XY plane
+        for (x, &u) in xkp.iter().zip(x_pos.iter()) {
+            draw_label(
+                (self.format_x)(x),
+                point([(0, u), (1, back[1]), (2, -back[2])]),
+            )?;
+        }
+        for (y, &u) in ykp.iter().zip(y_pos.iter()) {
+            draw_label(
+                (self.format_y)(y),
+                point([(0, -back[0]), (1, u), (2, back[2])]),
+            )?;
+        }
+        for (z, &u) in zkp.iter().zip(z_pos.iter()) {
+            draw_label(
+                (self.format_z)(z),
+                point([(0, -back[0]), (1, back[1]), (2, u)]),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<DB: DrawingBackend, X: Ranged, Y: Ranged, Z: Ranged> ChartContext<DB, RangedCoord3D<X, Y, Z>> {
+    /// Change the camera of the 3D chart
+    /// - `camera`: The new camera
+    pub fn set_camera(&mut self, camera: Camera) -> &mut Self {
+        self.drawing_area.as_coord_spec_mut().set_camera(camera);
+        self
+    }
+}
+
+impl<
+        DB: DrawingBackend,
+        XT: Debug,
+        YT: Debug,
+        ZT: Debug,
+        X: Ranged<ValueType = XT>,
+        Y: Ranged<ValueType = YT>,
+        Z: Ranged<ValueType = ZT>,
+    > ChartContext<DB, RangedCoord3D<X, Y, Z>>
+{
+    /// Initialize a 3D axis configuration object, which draws the three axis planes behind the
+    /// data with their meshes. The drawing can be finalized by calling `Mesh3DStyle::draw`
+    pub fn configure_axes(&mut self) -> Mesh3DStyle<'_, X, Y, Z, DB> {
+        Mesh3DStyle {
+            draw_labels: true,
+            n_x_labels: 10,
+            n_y_labels: 10,
+            n_z_labels: 10,
+            line_style: None,
+            axis_style: None,
+            panel_style: None,
             label_style: None,
             format_x: &|x| format!("{:?}", x),
             format_y: &|y| format!("{:?}", y),
+            format_z: &|z| format!("{:?}", z),
             target: Some(self),
-            _pahtom_data: PhantomData,
         }
     }
 }
 
+impl<
+        DB: DrawingBackend,
+        XT: Debug,
+        YT: Debug,
+        X: Ranged<ValueType = XT>,
+        Y: Ranged<ValueType = YT>,
+    > ChartContext<DB, RangedCoord<X, Y>>
+{
+    /// Initialize a mesh configuration object and mesh drawing can be finalized by calling
+    /// the function `MeshStyle::draw`
+    pub fn configure_mesh(&mut self) -> MeshStyle<X, Y, DB> {
+        MeshStyle::new(Some(self))
+    }
+}
+
 impl<DB: DrawingBackend, CT: ReverseCoordTranslate> ChartContext<DB, CT> {
     /// Convert the chart context into an closure that can be used for coordinate translation
     pub fn into_coord_trans(self) -> impl Fn(BackendCoord) -> Option<CT::From> {
@@ -365,30 +1456,19 @@ impl<DB: DrawingBackend, CT: ReverseCoordTranslate> ChartContext<DB, CT> {
     }
 }
 
-impl<DB: DrawingBackend, X: Ranged, Y: Ranged> ChartContext<DB, RangedCoord<X, Y>> {
-    /// Get the range of X axis
-    pub fn x_range(&self) -> Range<X::ValueType> {
-        self.drawing_area.get_x_range()
-    }
-
-    /// Get range of the Y axis
-    pub fn y_range(&self) -> Range<Y::ValueType> {
-        self.drawing_area.get_y_range()
-    }
-
+impl<DB: DrawingBackend, CT: CoordTranslate> ChartContext<DB, CT> {
     /// Get a reference of underlying plotting area
-    pub fn plotting_area(&self) -> &DrawingArea<DB, RangedCoord<X, Y>> {
+    pub fn plotting_area(&self) -> &DrawingArea<DB, CT> {
         &self.drawing_area
     }
 
-    /// Defines a series label area
+    /// Defines a series label area, where the legend is drawn by `configure_series_labels`
     pub fn define_series_label_area<'a, S: Into<ShapeStyle<'a>>>(
         &mut self,
         pos: (u32, u32),
         size: (u32, u32),
         bg_style: S,
     ) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
-        // TODO: we should be able to draw the label
         self.series_area = Some(self.drawing_area.strip_coord_spec().shrink(pos, size));
         let element = Rectangle::new([(0, 0), (size.0 as i32, size.1 as i32)], bg_style.into());
         self.series_area.as_ref().unwrap().draw(&element)
@@ -396,14 +1476,18 @@ impl<DB: DrawingBackend, X: Ranged, Y: Ranged> ChartContext<DB, RangedCoord<X, Y
 
     /// Maps the coordinate to the backend coordinate. This is typically used
     /// with an interactive chart.
-    pub fn backend_coord(&self, coord: &(X::ValueType, Y::ValueType)) -> BackendCoord {
+    pub fn backend_coord(&self, coord: &CT::From) -> BackendCoord {
         self.drawing_area.map_coordinate(coord)
     }
 
     /// Draw a data series. A data series in Plotters is abstracted as an iterator of elements
-    pub fn draw_series<E, R, S>(&self, series: S) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>>
+    /// - Returns: The annotation of the series, where the label and the legend glyph can be set
+    pub fn draw_series<E, R, S>(
+        &mut self,
+        series: S,
+    ) -> Result<&mut SeriesAnno, DrawingAreaErrorKind<DB::ErrorType>>
     where
-        for<'a> &'a E: PointCollection<'a, (X::ValueType, Y::ValueType)>,
+        for<'a> &'a E: PointCollection<'a, CT::From>,
         E: Drawable,
         R: Borrow<E>,
         S: IntoIterator<Item = R>,
@@ -411,9 +1495,78 @@ impl<DB: DrawingBackend, X: Ranged, Y: Ranged> ChartContext<DB, RangedCoord<X, Y
         for element in series {
             self.drawing_area.draw(element.borrow())?;
         }
+        self.series_anno.push(SeriesAnno::default());
+        Ok(self.series_anno.last_mut().unwrap())
+    }
+
+    /// Initialize a legend configuration object, the legend shows the labeled series and can be
+    /// drawn by calling `SeriesLabelStyle::draw`
+    pub fn configure_series_labels(&mut self) -> SeriesLabelStyle<'_, DB, CT> {
+        SeriesLabelStyle {
+            position: SeriesLabelPosition::UpperRight,
+            margin: 10,
+            border_style: None,
+            background_style: None,
+            label_style: None,
+            target: Some(self),
+        }
+    }
+}
+
+impl<DB: DrawingBackend, X: Ranged, Y: Ranged> ChartContext<DB, RangedCoord<X, Y>> {
+    /// Get the range of X axis
+    pub fn x_range(&self) -> Range<X::ValueType> {
+        self.drawing_area.get_x_range()
+    }
+
+    /// Get range of the Y axis
+    pub fn y_range(&self) -> Range<Y::ValueType> {
+        self.drawing_area.get_y_range()
+    }
+
+    /// Draw the axis titles in the label areas, just outside of the tick labels
+    /// - `extents`: The extents of the tick labels returned by `draw_mesh`
+    fn draw_axis_desc(
+        &self,
+        x_desc: Option<&str>,
+        y_desc: Option<&str>,
+        style: &TextStyle,
+        (x_extent, y_extent, right_y_extent): (i32, i32, i32),
+    ) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+        if let (Some(desc), Some(xl)) = (x_desc, self.x_label_area.as_ref()) {
+            let (tw, _) = xl.dim_in_pixel();
+            xl.draw_text(
+                desc,
+                &style.layout(TextLayout::new(HAnchor::Center, VAnchor::Top)),
+                (tw as i32 / 2, x_extent + AXIS_DESC_GAP),
+            )?;
+        }
+
+        if let Some(desc) = y_desc {
+            for (yl, right) in self
+                .y_label_area
+                .iter()
+                .map(|yl| (yl, false))
+                .chain(self.right_y_label_area.iter().map(|yl| (yl, true)))
+            {
+                let (tw, th) = yl.dim_in_pixel();
+                // The top of the rotated title faces the left side of the label area
+                let (x, v_anchor) = if right {
+                    (right_y_extent + AXIS_DESC_GAP, VAnchor::Top)
+                } else {
+                    (tw as i32 - y_extent - AXIS_DESC_GAP, VAnchor::Bottom)
+                };
+                let layout = TextLayout::new(HAnchor::Center, v_anchor).rotate(90.0);
+                yl.draw_text(desc, &style.layout(layout), (x, th as i32 / 2))?;
+            }
+        }
+
         Ok(())
     }
 
+    /// Draw the mesh lines, the axes and the tick labels
+    /// - Returns: How far the drawn tick labels reach from the X axis, the left Y axis and the
+    ///   right Y axis, in pixels
     #[allow(clippy::too_many_arguments)]
     fn draw_mesh<FmtLabel>(
         &mut self,
@@ -424,13 +1577,15 @@ impl<DB: DrawingBackend, X: Ranged, Y: Ranged> ChartContext<DB, RangedCoord<X, Y
         x_mesh: bool,
         y_mesh: bool,
         x_label_offset: i32,
+        x_label_rotation: f64,
         x_axis: bool,
         y_axis: bool,
         axis_style: &ShapeStyle,
-    ) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>>
+    ) -> Result<(i32, i32, i32), DrawingAreaErrorKind<DB::ErrorType>>
     where
         FmtLabel: FnMut(&MeshLine<X, Y>) -> Option<String>,
     {
+        let mut extents = (0, 0, 0);
         let mut x_labels = vec![];
         let mut y_labels = vec![];
         self.drawing_area.draw_mesh(
@@ -467,52 +1622,600 @@ impl<DB: DrawingBackend, X: Ranged, Y: Ranged> ChartContext<DB, RangedCoord<X, Y
             if x_axis {
                 xl.draw(&Path::new(vec![(0, 0), (tw as i32, 0)], axis_style.clone()))?;
             }
+            let x_label_style = label_style.layout(x_label_layout(x_label_rotation));
+            let mut labels = vec![];
             for (p, t) in x_labels {
-                let (w, _) = label_style.font.box_size(&t).unwrap_or((0, 0));
+                let x = p - x0 + x_label_offset;
+                let label_box = x_label_style
+                    .layout
+                    .outline(x_label_style.font, &t, (x, X_LABEL_TOP))
+                    .unwrap_or([(f64::from(x), f64::from(X_LABEL_TOP)); 4]);
+
+                let left = label_box.iter().fold(f64::MAX, |l, c| l.min(c.0));
+                let right = label_box.iter().fold(f64::MIN, |r, c| r.max(c.0));
 
-                if p - x0 + x_label_offset > 0 && p - x0 + x_label_offset + w as i32 / 2 < tw as i32
-                {
+                if left >= 0.0 && right < f64::from(tw) {
                     if x_axis {
                         xl.draw(&Path::new(
                             vec![(p - x0, 0), (p - x0, 5)],
                             axis_style.clone(),
                         ))?;
                     }
-                    xl.draw_text(
-                        &t,
-                        label_style,
-                        (p - x0 - w as i32 / 2 + x_label_offset, 10),
-                    )?;
+                    labels.push((x, t, label_box));
                 }
             }
+
+            let boxes: Vec<_> = labels.iter().map(|l| l.2).collect();
+            for (x, t, label_box) in labels.into_iter().step_by(label_step(&boxes)) {
+                xl.draw_text(&t, &x_label_style, (x, X_LABEL_TOP))?;
+                let bottom = label_box.iter().fold(f64::MIN, |b, c| b.max(c.1));
+                extents.0 = extents.0.max(bottom.ceil() as i32);
+            }
         }
 
-        if let Some(ref yl) = self.y_label_area {
+        for (yl, right) in self
+            .y_label_area
+            .iter()
+            .map(|yl| (yl, false))
+            .chain(self.right_y_label_area.iter().map(|yl| (yl, true)))
+        {
             let (tw, th) = yl.dim_in_pixel();
+            // The X position of the axis, and the direction the ticks and labels go from it
+            let (axis_x, dir) = if right { (0, 1) } else { (tw as i32, -1) };
             if y_axis {
                 yl.draw(&Path::new(
-                    vec![(tw as i32, 0), (tw as i32, th as i32)],
+                    vec![(axis_x, 0), (axis_x, th as i32)],
                     axis_style.clone(),
                 ))?;
             }
-            for (p, t) in y_labels {
-                let (w, h) = label_style.font.box_size(&t).unwrap_or((0, 0));
-                if p - y0 >= 0 && p - y0 - h as i32 / 2 <= th as i32 {
-                    yl.draw_text(
-                        &t,
-                        label_style,
-                        (tw as i32 - w as i32 - 10, p - y0 - h as i32 / 2),
-                    )?;
+            let (text_x, h_anchor) = if right {
+                (10, HAnchor::Left)
+            } else {
+                (tw as i32 - 10, HAnchor::Right)
+            };
+            let y_label_style = label_style.layout(TextLayout::new(h_anchor, VAnchor::Middle));
+            let mut labels = vec![];
+            for (p, t) in y_labels.iter() {
+                let pos = (text_x, p - y0);
+                let label_box = y_label_style
+                    .layout
+                    .outline(y_label_style.font, t, pos)
+                    .unwrap_or([(f64::from(pos.0), f64::from(pos.1)); 4]);
+                if p - y0 >= 0 && label_box[0].1 <= f64::from(th) {
+                    labels.push((t, pos, label_box));
                     if y_axis {
                         yl.draw(&Path::new(
-                            vec![(tw as i32 - 5, p - y0), (tw as i32, p - y0)],
+                            vec![(axis_x + 5 * dir, p - y0), (axis_x, p - y0)],
                             axis_style.clone(),
                         ))?;
                     }
                 }
             }
+
+            let boxes: Vec<_> = labels.iter().map(|l| l.2).collect();
+            for (t, pos, label_box) in labels.into_iter().step_by(label_step(&boxes)) {
+                yl.draw_text(t, &y_label_style, pos)?;
+                let extent = label_box
+                    .iter()
+                    .map(|c| f64::from(dir) * (c.0 - f64::from(axis_x)))
+                    .fold(f64::MIN, f64::max);
+                if right {
+                    extents.2 = extents.2.max(extent.ceil() as i32);
+                } else {
+                    extents.1 = extents.1.max(extent.ceil() as i32);
+                }
+            }
         }
 
-        Ok(())
+        Ok(extents)
+    }
+}
+
+impl<DB: DrawingBackend, X: Ranged, Y: Ranged> ChartContext<DB, RangedCoord<X, Y>>
+where
+    Range<X::ValueType>: Into<X>,
+{
+    /// Attach a secondary coordinate system to the chart. The secondary coordinate shares the
+    /// X axis with the primary one and has its own Y axis, whose labels are drawn in the right Y
+    /// label area.
+    /// - `y_spec`: The specification of the secondary Y axis
+    /// - Returns: The chart context with both coordinate systems
+    pub fn set_secondary_coord<Y2: AsRangedCoord>(
+        mut self,
+        y_spec: Y2,
+    ) -> DualCoordChartContext<DB, RangedCoord<X, Y>, RangedCoord<X, Y2::CoordDescType>> {
+        let mut pixel_range = self.drawing_area.get_pixel_range();
+        pixel_range.1 = pixel_range.1.end..pixel_range.1.start;
+
+        let secondary = ChartContext {
+            x_label_area: None,
+            y_label_area: None,
+            right_y_label_area: self.right_y_label_area.take(),
+            series_area: None,
+            series_anno: vec![],
+            drawing_area: self
+                .drawing_area
+                .strip_coord_spec()
+                .apply_coord_spec(RangedCoord::new(self.x_range(), y_spec, pixel_range)),
+        };
+
+        DualCoordChartContext {
+            primary: self,
+            secondary,
+        }
+    }
+}
+
+impl<DB: DrawingBackend, CT1: CoordTranslate, CT2: CoordTranslate> Deref
+    for DualCoordChartContext<DB, CT1, CT2>
+{
+    type Target = ChartContext<DB, CT1>;
+    fn deref(&self) -> &Self::Target {
+        &self.primary
+    }
+}
+
+impl<DB: DrawingBackend, CT1: CoordTranslate, CT2: CoordTranslate> DerefMut
+    for DualCoordChartContext<DB, CT1, CT2>
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.primary
+    }
+}
+
+impl<DB: DrawingBackend, CT1: CoordTranslate, X: Ranged, Y: Ranged>
+    DualCoordChartContext<DB, CT1, RangedCoord<X, Y>>
+{
+    /// Get a reference of the underlying plotting area of the secondary coordinate
+    pub fn secondary_plotting_area(&self) -> &DrawingArea<DB, RangedCoord<X, Y>> {
+        &self.secondary.drawing_area
+    }
+
+    /// Draw a data series on the secondary coordinate
+    pub fn draw_secondary_series<E, R, S>(
+        &mut self,
+        series: S,
+    ) -> Result<&mut SeriesAnno, DrawingAreaErrorKind<DB::ErrorType>>
+    where
+        for<'a> &'a E: PointCollection<'a, (X::ValueType, Y::ValueType)>,
+        E: Drawable,
+        R: Borrow<E>,
+        S: IntoIterator<Item = R>,
+    {
+        for element in series {
+            self.secondary.drawing_area.draw(element.borrow())?;
+        }
+        // The legend is drawn by the primary chart context, so it holds the annotations of both
+        self.primary.series_anno.push(SeriesAnno::default());
+        Ok(self.primary.series_anno.last_mut().unwrap())
+    }
+}
+
+impl<
+        DB: DrawingBackend,
+        CT1: CoordTranslate,
+        XT: Debug,
+        YT: Debug,
+        X: Ranged<ValueType = XT>,
+        Y: Ranged<ValueType = YT>,
+    > DualCoordChartContext<DB, CT1, RangedCoord<X, Y>>
+{
+    /// Initialize the configuration of the secondary Y axis, which can be finalized by calling
+    /// `MeshStyle::draw`. The secondary axis doesn't draw any mesh lines by default.
+    pub fn configure_secondary_axes(&mut self) -> MeshStyle<'_, X, Y, DB> {
+        let mut style = self.secondary.configure_mesh();
+        style.draw_x_mesh = false;
+        style.draw_y_mesh = false;
+        style
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::drawing::{DisplayList, DrawingCommand, RecordingBackend};
+    use crate::series::LineSeries;
+
+    type TextBox = ((i32, i32), (i32, i32));
+
+    /// The bounding boxes of all the recorded texts
+    fn text_boxes(list: &DisplayList) -> Vec<(&str, TextBox)> {
+        list.commands
+            .iter()
+            .filter_map(|command| match command {
+                DrawingCommand::Text {
+                    text,
+                    font,
+                    font_size,
+                    pos,
+                    layout,
+                    ..
+                } => {
+                    let font = FontDesc::new(font, *font_size);
+                    Some((
+                        text.as_str(),
+                        layout.bounding_box(&font, text, *pos).unwrap(),
+                    ))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_secondary_coord() {
+        let mut list = DisplayList::default();
+        let (primary, secondary, mapped) = {
+            let root: DrawingArea<_, _> = RecordingBackend::new(&mut list, (400, 300)).into();
+            let mut chart = ChartBuilder::on(&root)
+                .x_label_area_size(30)
+                .y_label_area_size(40)
+                .right_y_label_area_size(40)
+                .build_ranged(0..10, 0..10)
+                .unwrap()
+                .set_secondary_coord(0..1000);
+            chart.configure_mesh().draw().unwrap();
+            chart
+                .configure_secondary_axes()
+                .y_label_formatter(&|y| format!("{}ms", y))
+                .draw()
+                .unwrap();
+            chart
+                .draw_series(LineSeries::new(vec![(1, 1), (9, 9)], &RGBColor(255, 0, 0)))
+                .unwrap();
+            chart
+                .draw_secondary_series(LineSeries::new(
+                    vec![(1, 100), (9, 500)],
+                    &RGBColor(0, 0, 255),
+                ))
+                .unwrap();
+            let secondary = chart.secondary_plotting_area();
+            (
+                (chart.backend_coord(&(1, 1)), chart.backend_coord(&(9, 9))),
+                (
+                    secondary.map_coordinate(&(1, 100)),
+                    secondary.map_coordinate(&(9, 500)),
+                ),
+                (chart.backend_coord(&(1, 1)), chart.backend_coord(&(9, 5))),
+            )
+        };
+
+        // Both coordinates share the X axis and the plotting area
+        assert_eq!(secondary, mapped);
+        assert!(list.has_line(primary.0, primary.1));
+        assert!(list.has_line(secondary.0, secondary.1));
+
+        // The primary Y labels are on the left side, the secondary Y labels are on the right side
+        let texts = list.texts();
+        let (right, left): (Vec<_>, Vec<_>) =
+            texts.iter().partition(|(text, _)| text.ends_with("ms"));
+        assert_eq!(right.len(), 11);
+        assert!(right.iter().all(|(_, pos)| pos.0 == 370));
+        assert!(texts.contains(&("500ms", (370, mapped.1 .1))));
+        assert!(left.iter().all(|(_, pos)| pos.0 == 30 || pos.1 == 280));
+        assert!(list.has_line((360, 0), (360, 270)));
+
+        // The secondary axis doesn't draw any mesh lines, so only the primary mesh is drawn
+        // with lines, once for the fine mesh and once for the coarse mesh
+        let mesh_lines = list
+            .commands
+            .iter()
+            .filter(|command| matches!(command, DrawingCommand::Line { .. }))
+            .count();
+        assert_eq!(mesh_lines, 22 * 2);
+    }
+
+    #[test]
+    fn test_legend_layout() {
+        use SeriesLabelPosition::*;
+        let (size, area) = ((40, 20), (200, 100));
+        assert_eq!(UpperLeft.layout(size, area, 5), (5, 5));
+        assert_eq!(UpperMiddle.layout(size, area, 5), (80, 5));
+        assert_eq!(MiddleMiddle.layout(size, area, 5), (80, 40));
+        assert_eq!(MiddleRight.layout(size, area, 5), (155, 40));
+        assert_eq!(LowerLeft.layout(size, area, 5), (5, 75));
+        assert_eq!(LowerRight.layout(size, area, 5), (155, 75));
+        assert_eq!(Coordinate(-3, 7).layout(size, area, 5), (-3, 7));
+    }
+
+    #[test]
+    fn test_legend() {
+        let red = RGBColor(255, 0, 0);
+        let blue = RGBColor(0, 0, 255);
+        let font = FontDesc::new("Arial", 12.0);
+        let mut list = DisplayList::default();
+        {
+            let root: DrawingArea<_, _> = RecordingBackend::new(&mut list, (400, 300)).into();
+            let mut chart = ChartBuilder::on(&root).build_ranged(0..10, 0..10).unwrap();
+            chart
+                .draw_series(LineSeries::new(vec![(1, 1), (9, 9)], &red))
+                .unwrap()
+                .label("Sine")
+                .legend(LegendGlyph::line(
+                    Into::<ShapeStyle>::into(&red)
+                        .stroke_width(2)
+                        .dashed(&[4, 2]),
+                ));
+            chart
+                .draw_series(LineSeries::new(vec![(1, 9), (9, 1)], &blue))
+                .unwrap()
+                .label("Cosine wave")
+                .legend(LegendGlyph::marker(&blue, 3));
+            // The series without a label isn't shown in the legend
+            chart
+                .draw_series(LineSeries::new(vec![(1, 5), (9, 5)], &blue))
+                .unwrap();
+            chart
+                .configure_series_labels()
+                .position(SeriesLabelPosition::LowerRight)
+                .border_style(&RGBColor(0, 0, 0))
+                .label_style(&font)
+                .draw()
+                .unwrap();
+        }
+
+        // The legend measures the labels the same way, a label is empty without the font
+        let (sine_w, sine_h) = font.box_size("Sine").unwrap_or((0, 0));
+        let (cosine_w, cosine_h) = font.box_size("Cosine wave").unwrap_or((0, 0));
+        let row_height = sine_h.max(cosine_h).max(LEGEND_GLYPH_WIDTH / 2) as i32 + 5;
+        let (w, h) = (
+            15 + LEGEND_GLYPH_WIDTH as i32 + sine_w.max(cosine_w) as i32,
+            5 + row_height * 2,
+        );
+        let (x0, y0) = (400 - w - 10, 300 - h - 10);
+
+        // The legend is sized by the longest label and has one row per labeled series
+        assert!(list.commands.iter().any(|command| match command {
+            DrawingCommand::Rect {
+                upper_left,
+                bottom_right,
+                fill: false,
+                ..
+            } => *upper_left == (x0, y0) && *bottom_right == (x0 + w, y0 + h),
+            _ => false,
+        }));
+        let middle = |row: i32| y0 + 5 + row_height * row + (row_height - 5) / 2;
+        assert_eq!(
+            list.texts(),
+            vec![
+                ("Sine", (x0 + 30, middle(0))),
+                ("Cosine wave", (x0 + 30, middle(1)))
+            ]
+        );
+
+        // The glyphs are centered in the glyph column of their rows
+        let center = (x0 + 15, middle(1));
+        assert_eq!(list.count_circles_in(center, center), 1);
+        // The line glyph keeps the stroke width and the dash pattern of the series
+        assert!(list.commands.iter().any(|command| match command {
+            DrawingCommand::Path { points, style } => {
+                points == &[(x0 + 5, middle(0)), (x0 + 25, middle(0))]
+                    && style.stroke_width == 2
+                    && style.stroke_dash == vec![4, 2]
+            }
+            _ => false,
+        }));
+    }
+
+    #[test]
+    fn test_axis_desc() {
+        let mut list = DisplayList::default();
+        {
+            let root: DrawingArea<_, _> = RecordingBackend::new(&mut list, (400, 300)).into();
+            let mut chart = ChartBuilder::on(&root)
+                .x_label_area_size(50)
+                .y_label_area_size(80)
+                .right_y_label_area_size(80)
+                .build_ranged(0..10, 0..100_000)
+                .unwrap();
+            chart
+                .configure_mesh()
+                .x_desc("Time")
+                .y_desc("Value")
+                .draw()
+                .unwrap();
+        }
+
+        let boxes = text_boxes(&list);
+        let (titles, ticks): (Vec<_>, Vec<_>) = boxes
+            .iter()
+            .partition(|(text, _)| *text == "Time" || *text == "Value");
+        assert_eq!(titles.len(), 3);
+        assert!(ticks.len() > 10);
+
+        // Each title is next to the tick labels in its label area, without covering them
+        let areas = [
+            ((0, 250), (400, 300)),
+            ((0, 0), (80, 250)),
+            ((320, 0), (400, 250)),
+        ];
+        let inside = |b: &TextBox, area: &TextBox| {
+            let center = (((b.0).0 + (b.1).0) / 2, ((b.0).1 + (b.1).1) / 2);
+            center.0 >= (area.0).0
+                && center.0 < (area.1).0
+                && center.1 >= (area.0).1
+                && center.1 < (area.1).1
+        };
+        for (title, title_box) in titles.iter() {
+            let area = areas.iter().find(|area| inside(title_box, area)).unwrap();
+            let labels = ticks
+                .iter()
+                .map(|(_, tick_box)| *tick_box)
+                .filter(|tick_box| inside(tick_box, area))
+                .fold(((i32::MAX, i32::MAX), (i32::MIN, i32::MIN)), |u, b| {
+                    (
+                        ((u.0).0.min((b.0).0), (u.0).1.min((b.0).1)),
+                        ((u.1).0.max((b.1).0), (u.1).1.max((b.1).1)),
+                    )
+                });
+            // The distance is negative if the boxes intersect
+            let dx = ((title_box.0).0 - (labels.1).0).max((labels.0).0 - (title_box.1).0);
+            let dy = ((title_box.0).1 - (labels.1).1).max((labels.0).1 - (title_box.1).1);
+            assert_eq!(dx.max(dy), AXIS_DESC_GAP, "{} {:?}", title, title_box);
+            assert!((title_box.0).0 >= 0 && (title_box.1).0 <= 400);
+            assert!((title_box.0).1 >= 0 && (title_box.1).1 <= 300);
+        }
+    }
+
+    /// An axis-aligned label box
+    fn rect(x: f64, y: f64, w: f64, h: f64) -> LabelBox {
+        [(x, y), (x + w, y), (x + w, y + h), (x, y + h)]
+    }
+
+    #[test]
+    fn test_label_thinning() {
+        // The labels must be at least LABEL_GAP pixels apart
+        let a = rect(0.0, 0.0, 10.0, 10.0);
+        assert!(!label_boxes_intersect(&a, &rect(15.0, 0.0, 10.0, 10.0)));
+        assert!(label_boxes_intersect(&a, &rect(14.0, 0.0, 10.0, 10.0)));
+        assert!(label_boxes_intersect(&a, &rect(5.0, 5.0, 10.0, 10.0)));
+        assert!(!label_boxes_intersect(&a, &rect(0.0, 20.0, 10.0, 10.0)));
+
+        // Rotated labels are separated along their own axes, even if their bounding boxes overlap
+        let rotate = |b: LabelBox, dx: f64| {
+            let (sin, cos) = std::f64::consts::FRAC_PI_4.sin_cos();
+            let mut corners = b;
+            for c in corners.iter_mut() {
+                *c = (c.0 * cos - c.1 * sin + dx, c.0 * sin + c.1 * cos);
+            }
+            corners
+        };
+        let long = rect(0.0, 0.0, 40.0, 4.0);
+        assert!(!label_boxes_intersect(
+            &rotate(long, 0.0),
+            &rotate(long, 20.0)
+        ));
+        assert!(label_boxes_intersect(
+            &rotate(long, 0.0),
+            &rotate(long, 10.0)
+        ));
+
+        let row = |spacing: f64| -> Vec<_> {
+            (0..5)
+                .map(|idx| rect(f64::from(idx) * spacing, 0.0, 10.0, 10.0))
+                .collect()
+        };
+        assert_eq!(label_step(&row(20.0)), 1);
+        assert_eq!(label_step(&row(12.0)), 2);
+        assert_eq!(label_step(&row(5.0)), 3);
+        assert_eq!(label_step(&row(0.0)), 5);
+        assert_eq!(label_step(&[]), 1);
+    }
+
+    /// Draw a chart with long X labels and get the boxes of the X labels
+    fn draw_long_x_labels(list: &mut DisplayList, rotation: f64) -> Vec<TextBox> {
+        list.commands.clear();
+        {
+            let root: DrawingArea<_, _> = RecordingBackend::new(list, (400, 300)).into();
+            let mut chart = ChartBuilder::on(&root)
+                .x_label_area_size(100)
+                .build_ranged(0..10, 0..10)
+                .unwrap();
+            chart
+                .configure_mesh()
+                .x_label_rotation(rotation)
+                .x_label_formatter(&|x| format!("Sample #{}", x))
+                .draw()
+                .unwrap();
+        }
+        text_boxes(list)
+            .into_iter()
+            .filter(|(text, _)| text.starts_with("Sample"))
+            .map(|(_, b)| b)
+            .collect()
+    }
+
+    #[test]
+    fn test_x_label_rotation() {
+        let mut list = DisplayList::default();
+        let overlap =
+            |a: &TextBox, b: &TextBox| (a.0).0 < (b.1).0 && (b.0).0 < (a.1).0 && (a.0).1 < (b.1).1;
+
+        // The unrotated labels don't fit side by side, so every other label is dropped
+        let flat = draw_long_x_labels(&mut list, 0.0);
+        assert!(flat.len() >= 2 && flat.len() <= 5, "{:?}", flat);
+        assert!(flat.windows(2).all(|w| !overlap(&w[0], &w[1])));
+
+        // The rotated labels hang down from their ticks, inside of the X label area
+        let mut rotated_90 = vec![];
+        for &rotation in [30.0, 90.0].iter() {
+            let rotated = draw_long_x_labels(&mut list, rotation);
+            assert!(rotated.len() > flat.len(), "{:?}", rotated);
+            for b in rotated.iter() {
+                assert!((b.0).0 >= 0 && (b.1).0 <= 400, "{:?}", b);
+                assert!((b.0).1 > 200 && (b.1).1 <= 300, "{:?}", b);
+            }
+            rotated_90 = rotated;
+        }
+        // The vertical labels are separated horizontally, while the bounding boxes of the labels
+        // rotated by 30 degrees overlap each other
+        assert!(rotated_90.windows(2).all(|w| !overlap(&w[0], &w[1])));
+        assert!(list.commands.iter().any(|command| match command {
+            DrawingCommand::Text { layout, .. } => layout.rotation == 90.0,
+            _ => false,
+        }));
+    }
+
+    #[test]
+    fn test_build_ranged_with_mesh() {
+        let draw = |list: &mut DisplayList, long: bool| {
+            list.commands.clear();
+            let root: DrawingArea<_, _> = RecordingBackend::new(list, (400, 300)).into();
+            let chart = ChartBuilder::on(&root)
+                .margin(10)
+                .x_label_area_size(1)
+                .y_label_area_size(1)
+                .build_ranged_with_mesh(0..10, 0..10, |mesh| {
+                    mesh.x_desc("Time").y_desc("Value");
+                    if long {
+                        mesh.x_label_rotation(90.0)
+                            .x_label_formatter(&|x| format!("Day {}", x))
+                            .y_label_formatter(&|y| format!("{} requests", y));
+                    }
+                })
+                .unwrap();
+            let (x, y) = chart.plotting_area().get_base_pixel();
+            let (_, h) = chart.plotting_area().dim_in_pixel();
+            // The label area sizes, which are between the plotting area and the margin
+            assert_eq!(y, 10);
+            (x - 10, 300 - 20 - h as i32)
+        };
+
+        let mut list = DisplayList::default();
+        let (short_y, short_x) = draw(&mut list, false);
+        let (long_y, long_x) = draw(&mut list, true);
+
+        // The label areas grow with the formatted and rotated labels, while the Y labels keep
+        // their 10 pixels of distance from the axis
+        assert!(short_y > 1 && long_y > short_y);
+        assert!(short_x > 1 && long_x > short_x);
+        assert!(list.texts().contains(&("10 requests", (long_y, 10))));
+        assert_eq!(
+            list.texts()
+                .iter()
+                .filter(|(t, _)| t.starts_with("Day"))
+                .count(),
+            9
+        );
+
+        // Everything fits into the label areas, and the titles don't cover the tick labels
+        let boxes = text_boxes(&list);
+        let (titles, ticks): (Vec<_>, Vec<_>) = boxes
+            .iter()
+            .partition(|(text, _)| *text == "Time" || *text == "Value");
+        for (text, b) in boxes.iter() {
+            assert!((b.0).0 >= 10 && (b.1).0 <= 390, "{} {:?}", text, b);
+            assert!((b.0).1 >= 0 && (b.1).1 <= 290, "{} {:?}", text, b);
+        }
+        for (_, title_box) in titles.iter() {
+            for (_, tick_box) in ticks.iter() {
+                assert!(
+                    (title_box.1).0 <= (tick_box.0).0
+                        || (tick_box.1).0 <= (title_box.0).0
+                        || (title_box.1).1 <= (tick_box.0).1
+                        || (tick_box.1).1 <= (title_box.0).1
+                );
+            }
+        }
     }
 }
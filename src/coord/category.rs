@@ -0,0 +1,320 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+use std::rc::Rc;
+
+use super::{AsRangedCoord, DescreteRanged, Ranged, ReversableRanged};
+
+/// The ranged coordinate for categorical values. Each category takes a band of the same width
+/// and the categories are mapped to the center of their bands, while the values returned by
+/// `Category::band_start` are mapped to the start of their bands. Histograms use the band starts,
+/// so that each bar covers the band of its category.
+#[derive(Clone)]
+pub struct RangedCategory<T> {
+    categories: Rc<Vec<T>>,
+    range: Range<usize>,
+}
+
+/// A value on a categorical axis, which is one of the categories of a `RangedCategory`
+#[derive(Clone)]
+pub struct Category<T> {
+    categories: Rc<Vec<T>>,
+    idx: usize,
+    /// If this value is mapped to the start of the band rather than its center
+    at_band_start: bool,
+}
+
+impl<T> Category<T> {
+    /// Get the index of this category in the category list
+    pub fn index(&self) -> usize {
+        self.idx
+    }
+
+    /// Get the label of this category. The value after the last category has no label.
+    pub fn label(&self) -> Option<&T> {
+        self.categories.get(self.idx)
+    }
+
+    /// Get the value at the start of the band of this category, which is where a bar of this
+    /// category begins
+    pub fn band_start(&self) -> Self {
+        Self {
+            categories: self.categories.clone(),
+            idx: self.idx,
+            at_band_start: true,
+        }
+    }
+
+    /// Get the value at the end of the band of this category, which is where a bar of this
+    /// category ends
+    pub fn band_end(&self) -> Self {
+        Self {
+            categories: self.categories.clone(),
+            idx: self.idx + 1,
+            at_band_start: true,
+        }
+    }
+}
+
+/// The default category doesn't belong to any coordinate, it only allows the categories to be
+/// used as the keys of a `Histogram`
+impl<T> Default for Category<T> {
+    fn default() -> Self {
+        Self {
+            categories: Rc::new(vec![]),
+            idx: 0,
+            at_band_start: false,
+        }
+    }
+}
+
+impl<T> PartialEq for Category<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.idx == other.idx
+            && self.at_band_start == other.at_band_start
+            && Rc::ptr_eq(&self.categories, &other.categories)
+    }
+}
+
+impl<T> Eq for Category<T> {}
+
+impl<T> Hash for Category<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.idx.hash(state)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Category<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.label() {
+            Some(label) => label.fmt(f),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Category<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.label() {
+            Some(label) => label.fmt(f),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: PartialEq> RangedCategory<T> {
+    /// Find the category with the given label
+    /// - `label`: The label to look up
+    /// - Returns: The category value, or `None` if the label isn't one of the categories
+    pub fn get(&self, label: &T) -> Option<Category<T>> {
+        let categories = &self.categories[self.range.clone()];
+        categories
+            .iter()
+            .position(|c| c == label)
+            .map(|idx| self.value(self.range.start + idx))
+    }
+}
+
+impl<T> RangedCategory<T> {
+    fn value(&self, idx: usize) -> Category<T> {
+        Category {
+            categories: self.categories.clone(),
+            idx,
+            at_band_start: false,
+        }
+    }
+
+    /// Get all the categories of this coordinate in order
+    pub fn values(&self) -> impl Iterator<Item = Category<T>> + '_ {
+        self.range.clone().map(move |idx| self.value(idx))
+    }
+}
+
+impl<T> From<Vec<T>> for RangedCategory<T> {
+    fn from(categories: Vec<T>) -> Self {
+        let range = 0..categories.len();
+        Self {
+            categories: Rc::new(categories),
+            range,
+        }
+    }
+}
+
+impl<T: Clone> From<&[T]> for RangedCategory<T> {
+    fn from(categories: &[T]) -> Self {
+        categories.to_vec().into()
+    }
+}
+
+impl<T> From<Range<Category<T>>> for RangedCategory<T> {
+    fn from(range: Range<Category<T>>) -> Self {
+        Self {
+            categories: range.start.categories,
+            range: range.start.idx..range.end.idx,
+        }
+    }
+}
+
+impl<T> Ranged for RangedCategory<T> {
+    type ValueType = Category<T>;
+
+    fn range(&self) -> Range<Category<T>> {
+        self.value(self.range.start).band_start()..self.value(self.range.end).band_start()
+    }
+
+    fn map(&self, value: &Category<T>, limit: (i32, i32)) -> i32 {
+        let bands = self.range.len() as f64;
+        if bands == 0.0 {
+            return limit.1;
+        }
+
+        let offset = if value.at_band_start { 0.0 } else { 0.5 };
+        let pos = (value.idx as f64 - self.range.start as f64 + offset) / bands;
+        limit.0 + (f64::from(limit.1 - limit.0) * pos).round() as i32
+    }
+
+    fn key_points(&self, max_points: usize) -> Vec<Category<T>> {
+        if max_points == 0 {
+            return vec![];
+        }
+
+        let step = (self.range.len() + max_points - 1) / max_points;
+        self.range
+            .clone()
+            .step_by(step.max(1))
+            .map(|idx| self.value(idx))
+            .collect()
+    }
+}
+
+impl<T> DescreteRanged for RangedCategory<T> {
+    fn next_value(this: &Category<T>) -> Category<T> {
+        Category {
+            categories: this.categories.clone(),
+            idx: this.idx + 1,
+            at_band_start: this.at_band_start,
+        }
+    }
+
+    fn band_start(this: Category<T>) -> Category<T> {
+        this.band_start()
+    }
+}
+
+impl<T> ReversableRanged for RangedCategory<T> {
+    fn unmap(&self, input: i32, limit: (i32, i32)) -> Option<Category<T>> {
+        if input < limit.0.min(limit.1) || input > limit.0.max(limit.1) || limit.0 == limit.1 {
+            return None;
+        }
+
+        let bands = self.range.len();
+        if bands == 0 {
+            return None;
+        }
+
+        let offset = f64::from(input - limit.0) / f64::from(limit.1 - limit.0);
+        let band = ((offset * bands as f64) as usize).min(bands - 1);
+
+        Some(self.value(self.range.start + band))
+    }
+}
+
+impl<T> AsRangedCoord for Vec<T> {
+    type CoordDescType = RangedCategory<T>;
+    type Value = Category<T>;
+}
+
+impl<T: Clone> AsRangedCoord for &[T] {
+    type CoordDescType = RangedCategory<T>;
+    type Value = Category<T>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::chart::ChartBuilder;
+    use crate::drawing::{DisplayList, DrawingArea, DrawingCommand, RecordingBackend};
+    use crate::series::Histogram;
+    use crate::style::{RGBColor, ShapeStyle};
+
+    #[test]
+    fn test_category_map() {
+        let coord: RangedCategory<_> = vec!["us-east", "eu-west", "ap-south", "sa-east"].into();
+
+        let eu = coord.get(&"eu-west").unwrap();
+        assert_eq!(eu.index(), 1);
+        assert_eq!(coord.map(&eu, (0, 400)), 150);
+        assert_eq!(coord.unmap(120, (0, 400)), Some(eu.clone()));
+        assert_eq!(coord.unmap(400, (0, 400)), coord.get(&"sa-east"));
+        assert_eq!(RangedCategory::next_value(&eu).label(), Some(&"ap-south"));
+
+        let kp = coord.key_points(10);
+        assert_eq!(kp.len(), 4);
+        assert_eq!(format!("{:?}", kp[2]), "\"ap-south\"");
+        assert_eq!(coord.key_points(2).len(), 2);
+    }
+
+    #[test]
+    fn test_category_band() {
+        let coord: RangedCategory<_> = vec!["a", "b", "c", "d"].into();
+        let b = coord.get(&"b").unwrap();
+        assert_eq!(coord.map(&b.band_start(), (0, 400)), 100);
+        assert_eq!(coord.map(&b.band_end(), (0, 400)), 200);
+        assert_eq!(RangedCategory::band_start(b.clone()), b.band_start());
+        assert_ne!(b.band_start(), b);
+
+        let range = coord.range();
+        assert_eq!(coord.map(&range.start, (0, 400)), 0);
+        assert_eq!(coord.map(&range.end, (0, 400)), 400);
+    }
+
+    #[test]
+    fn test_category_histogram() {
+        let labels = vec!["us-east", "eu-west", "ap-south", "sa-east"];
+        let color = RGBColor(0, 0, 255);
+        let mut list = DisplayList::default();
+        let (x_range, y_range) = {
+            let root: DrawingArea<_, _> = RecordingBackend::new(&mut list, (400, 300)).into();
+            let mut chart = ChartBuilder::on(&root)
+                .x_label_area_size(30)
+                .y_label_area_size(40)
+                .build_ranged(labels.clone(), 0..10)
+                .unwrap();
+            chart.configure_mesh().draw().unwrap();
+            let coord: RangedCategory<_> = labels.clone().into();
+            chart
+                .draw_series(Histogram::<RangedCategory<_>, _>::new(
+                    coord.values().zip(vec![3, 5, 7, 9]),
+                    0,
+                    Into::<ShapeStyle>::into(&color).filled(),
+                ))
+                .unwrap();
+            chart.plotting_area().get_pixel_range()
+        };
+
+        // There is one bar per category, and the bars tile the plotting area from edge to edge.
+        // Like the clip region of the plotting area, the pixel range includes its end.
+        let mut bars: Vec<_> = list
+            .with_color(&color)
+            .filter_map(|command| match command {
+                DrawingCommand::Rect {
+                    upper_left,
+                    bottom_right,
+                    ..
+                } => Some((*upper_left, *bottom_right)),
+                _ => None,
+            })
+            .collect();
+        bars.sort();
+        assert_eq!(bars.len(), labels.len());
+        assert_eq!((bars[0].0).0, x_range.start);
+        assert_eq!((bars[3].1).0, x_range.end);
+        for (bar, next) in bars.iter().zip(bars.iter().skip(1)) {
+            assert!((bar.1).0 <= (next.0).0);
+        }
+        for (upper_left, bottom_right) in bars {
+            assert!(x_range.start <= upper_left.0 && bottom_right.0 <= x_range.end);
+            assert!(y_range.start <= upper_left.1 && bottom_right.1 <= y_range.end);
+        }
+    }
+}
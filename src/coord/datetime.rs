@@ -1,21 +1,40 @@
 /// The datetime coordinates
-use chrono::{Date, DateTime, Duration, TimeZone};
+use chrono::{Date, DateTime, Datelike, Duration, TimeZone, Timelike, Weekday};
 use std::ops::Range;
 
-use super::Ranged;
+use super::{AsRangedCoord, Ranged, ReversableRanged};
 
 /// The ranged coordinate for date
-pub struct RangedDate<Z: TimeZone>(Date<Z>, Date<Z>);
+pub struct RangedDate<Z: TimeZone>(Date<Z>, Date<Z>, Weekday);
 
 /// The ranged coordinate for the date and time
 pub struct RangedDateTime<Z: TimeZone>(DateTime<Z>, DateTime<Z>);
 
 impl<Z: TimeZone> From<Range<Date<Z>>> for RangedDate<Z> {
     fn from(range: Range<Date<Z>>) -> Self {
-        Self(range.start, range.end)
+        Self(range.start, range.end, Weekday::Mon)
     }
 }
 
+impl<Z: TimeZone> RangedDate<Z> {
+    /// Set the first day of the week, weekly key points are aligned to this day.
+    /// By default the week starts on Monday.
+    pub fn with_week_start(self, week_start: Weekday) -> Self {
+        Self(self.0, self.1, week_start)
+    }
+}
+
+/// The candidate tick intervals for a date axis once weeks are too dense, in months
+const DATE_MONTH_STEPS: [i32; 6] = [1, 3, 6, 12, 60, 120];
+
+/// Get the first day of the month `month_idx` counted from year 0, with the time zone of `date`
+fn first_day_of_month<Z: TimeZone>(date: &Date<Z>, month_idx: i32) -> Option<Date<Z>> {
+    date.with_day(1)?
+        .with_month(1)?
+        .with_year(month_idx.div_euclid(12))?
+        .with_month(month_idx.rem_euclid(12) as u32 + 1)
+}
+
 impl<Z: TimeZone> Ranged for RangedDate<Z> {
     type ValueType = Date<Z>;
 
@@ -44,17 +63,220 @@ impl<Z: TimeZone> Ranged for RangedDate<Z> {
         }
 
         if total_weeks > 0 && total_weeks as usize <= max_points {
-            for day_idx in 0..=total_weeks {
-                ret.push(self.0.clone() + Duration::weeks(day_idx));
+            let skip =
+                (7 + self.2.num_days_from_monday() - self.0.weekday().num_days_from_monday()) % 7;
+            let mut current = self.0.clone() + Duration::days(i64::from(skip));
+            while current <= self.1 {
+                ret.push(current.clone());
+                current += Duration::weeks(1);
             }
             return ret;
         }
 
+        // The first and last month that has its first day in the range
+        let first_month =
+            self.0.year() * 12 + self.0.month0() as i32 + if self.0.day() == 1 { 0 } else { 1 };
+        let last_month = self.1.year() * 12 + self.1.month0() as i32;
+
+        if first_month > last_month || max_points == 0 {
+            return ret;
+        }
+
+        let mut steps = DATE_MONTH_STEPS
+            .iter()
+            .cloned()
+            .chain((1..).map(|n| 1200 * n));
+        let step = loop {
+            let step = steps.next().unwrap();
+            // Ticks are aligned to multiples of the step, i.e. quarters, years and decades
+            let first = first_month + (step - first_month.rem_euclid(step)) % step;
+            if first > last_month || ((last_month - first) / step + 1) as usize <= max_points {
+                break step;
+            }
+        };
+
+        let mut month_idx = first_month + (step - first_month.rem_euclid(step)) % step;
+        while month_idx <= last_month {
+            if let Some(date) = first_day_of_month(&self.0, month_idx) {
+                ret.push(date);
+            }
+            month_idx += step;
+        }
+
         ret
     }
 }
 
-impl<Z: TimeZone> super::AsRangedCoord for Range<Date<Z>> {
+impl<Z: TimeZone> AsRangedCoord for Range<Date<Z>> {
     type CoordDescType = RangedDate<Z>;
     type Value = Date<Z>;
 }
+
+impl<Z: TimeZone> From<Range<DateTime<Z>>> for RangedDateTime<Z> {
+    fn from(range: Range<DateTime<Z>>) -> Self {
+        Self(range.start, range.end)
+    }
+}
+
+/// The candidate tick intervals for a date time axis, in seconds
+const DATETIME_STEPS: [i64; 14] = [
+    1,
+    5,
+    15,
+    30,
+    60,
+    300,
+    900,
+    1800,
+    3600,
+    3 * 3600,
+    6 * 3600,
+    12 * 3600,
+    86400,
+    7 * 86400,
+];
+
+impl<Z: TimeZone> Ranged for RangedDateTime<Z> {
+    type ValueType = DateTime<Z>;
+
+    fn range(&self) -> Range<DateTime<Z>> {
+        self.0.clone()..self.1.clone()
+    }
+
+    fn map(&self, value: &Self::ValueType, limit: (i32, i32)) -> i32 {
+        let total_ms = (self.1.clone() - self.0.clone()).num_milliseconds() as f64;
+        let value_ms = (value.clone() - self.0.clone()).num_milliseconds() as f64;
+
+        if total_ms == 0.0 {
+            return limit.1;
+        }
+
+        limit.0 + (f64::from(limit.1 - limit.0) * value_ms / total_ms + 1e-3).floor() as i32
+    }
+
+    fn key_points(&self, max_points: usize) -> Vec<Self::ValueType> {
+        let mut ret = vec![];
+
+        let total_secs = (self.1.clone() - self.0.clone()).num_seconds();
+        if total_secs <= 0 || max_points == 0 {
+            return ret;
+        }
+
+        let fits = |step: i64| (total_secs / step + 1) as usize <= max_points;
+        let step = match DATETIME_STEPS.iter().find(|&&step| fits(step)) {
+            Some(&step) => step,
+            None => {
+                let days = total_secs / 86400 / max_points as i64 + 1;
+                86400 * days
+            }
+        };
+
+        // Align the first key point to a multiple of the step in local time, days
+        // or longer steps are aligned to the midnight
+        let align = step.min(86400);
+        let local = self.0.naive_local();
+        let sec_of_day = i64::from(local.num_seconds_from_midnight());
+        let mut current = self.0.clone() + Duration::seconds((align - sec_of_day % align) % align)
+            - Duration::nanoseconds(i64::from(local.nanosecond()));
+        if current < self.0 {
+            current += Duration::seconds(step);
+        }
+
+        while current <= self.1 {
+            ret.push(current.clone());
+            current += Duration::seconds(step);
+        }
+
+        ret
+    }
+}
+
+impl<Z: TimeZone> ReversableRanged for RangedDateTime<Z> {
+    fn unmap(&self, input: i32, limit: (i32, i32)) -> Option<Self::ValueType> {
+        if input < limit.0.min(limit.1) || input > limit.0.max(limit.1) || limit.0 == limit.1 {
+            return None;
+        }
+
+        let total_ms = (self.1.clone() - self.0.clone()).num_milliseconds() as f64;
+        let logical_offset = f64::from(input - limit.0) / f64::from(limit.1 - limit.0);
+
+        Some(self.0.clone() + Duration::milliseconds((total_ms * logical_offset) as i64))
+    }
+}
+
+impl<Z: TimeZone> AsRangedCoord for Range<DateTime<Z>> {
+    type CoordDescType = RangedDateTime<Z>;
+    type Value = DateTime<Z>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::Utc;
+
+    fn time(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_datetime_key_points() {
+        let coord: RangedDateTime<_> =
+            (time("2019-06-01T10:03:20Z")..time("2019-06-01T12:00:00Z")).into();
+
+        let kp = coord.key_points(10);
+        assert_eq!(kp.len(), 8);
+        assert_eq!(kp[0], time("2019-06-01T10:15:00Z"));
+        assert_eq!(kp[7], time("2019-06-01T12:00:00Z"));
+
+        let kp = coord.key_points(200);
+        assert_eq!(kp[0], time("2019-06-01T10:04:00Z"));
+        assert_eq!(kp[1] - kp[0], Duration::minutes(1));
+
+        let coord: RangedDateTime<_> =
+            (time("2019-06-01T10:03:20Z")..time("2019-06-03T10:00:00Z")).into();
+        let kp = coord.key_points(3);
+        assert_eq!(
+            kp,
+            vec![time("2019-06-02T00:00:00Z"), time("2019-06-03T00:00:00Z")]
+        );
+    }
+
+    fn date(s: &str) -> Date<Utc> {
+        time(&format!("{}T00:00:00Z", s)).date()
+    }
+
+    #[test]
+    fn test_date_key_points() {
+        let coord: RangedDate<_> = (date("2019-06-05")..date("2019-07-20")).into();
+        let kp = coord.key_points(10);
+        assert_eq!(kp[0], date("2019-06-10"));
+        assert_eq!(kp.len(), 6);
+
+        let coord = coord.with_week_start(Weekday::Sun);
+        assert_eq!(coord.key_points(10)[0], date("2019-06-09"));
+
+        let coord: RangedDate<_> = (date("2016-02-15")..date("2019-07-20")).into();
+        assert_eq!(coord.key_points(50)[0], date("2016-03-01"));
+        assert_eq!(coord.key_points(20)[0], date("2016-04-01"));
+        assert_eq!(
+            coord.key_points(5),
+            vec![date("2017-01-01"), date("2018-01-01"), date("2019-01-01")]
+        );
+
+        let coord: RangedDate<_> = (date("1950-02-15")..date("2019-07-20")).into();
+        assert_eq!(coord.key_points(10)[0], date("1960-01-01"));
+    }
+
+    #[test]
+    fn test_datetime_map_unmap() {
+        let coord: RangedDateTime<_> =
+            (time("2019-06-01T00:00:00Z")..time("2019-06-01T01:40:00Z")).into();
+
+        assert_eq!(coord.map(&time("2019-06-01T00:50:00Z"), (0, 100)), 50);
+        assert_eq!(
+            coord.unmap(25, (0, 100)),
+            Some(time("2019-06-01T00:25:00Z"))
+        );
+        assert_eq!(coord.unmap(101, (0, 100)), None);
+    }
+}
@@ -3,18 +3,24 @@
 */
 use crate::drawing::backend::BackendCoord;
 
+mod category;
 #[cfg(feature = "chrono")]
 mod datetime;
 mod logarithmic;
 mod numeric;
+mod polar;
 mod ranged;
+mod ranged3d;
 
+pub use category::{Category, RangedCategory};
 #[cfg(feature = "chrono")]
 pub use datetime::{RangedDate, RangedDateTime};
 pub use numeric::{
     RangedCoordf32, RangedCoordf64, RangedCoordi32, RangedCoordi64, RangedCoordu32, RangedCoordu64,
 };
+pub use polar::PolarCoord;
 pub use ranged::{AsRangedCoord, DescreteRanged, MeshLine, Ranged, RangedCoord, ReversableRanged};
+pub use ranged3d::{Camera, RangedCoord3D};
 
 pub use logarithmic::{LogCoord, LogRange, LogScalable};
 
@@ -0,0 +1,106 @@
+use super::{CoordTranslate, Ranged, ReversableRanged, ReverseCoordTranslate};
+use crate::drawing::backend::BackendCoord;
+
+use std::ops::Range;
+
+/// The polar coordinate system. A point is described as `(angle, radius)`, where the angle is
+/// in degrees, starting from the top of the chart and going clockwise, and the radius is
+/// mapped by any ranged value from the center to the outer circle.
+pub struct PolarCoord<R: Ranged> {
+    logic_radius: R,
+    center: BackendCoord,
+    back_radius: i32,
+}
+
+impl<R: Ranged> PolarCoord<R> {
+    /// Create a new polar coordinate system
+    /// - `logic_radius`: The range of the radius
+    /// - `center`: The center of the coordinate in backend pixels
+    /// - `back_radius`: The radius of the outer circle in pixels
+    pub fn new<IntoR: Into<R>>(
+        logic_radius: IntoR,
+        center: BackendCoord,
+        back_radius: u32,
+    ) -> Self {
+        Self {
+            logic_radius: logic_radius.into(),
+            center,
+            back_radius: back_radius as i32,
+        }
+    }
+
+    /// Get the range of the radius
+    pub fn get_radius_range(&self) -> Range<R::ValueType> {
+        self.logic_radius.range()
+    }
+
+    /// Get the center of the coordinate in backend pixels
+    pub fn get_center(&self) -> BackendCoord {
+        self.center
+    }
+
+    /// Get the radius of the outer circle in pixels
+    pub fn get_back_radius(&self) -> u32 {
+        self.back_radius as u32
+    }
+
+    /// Map a radius value to its distance from the center in pixels
+    pub fn map_radius(&self, radius: &R::ValueType) -> i32 {
+        self.logic_radius.map(radius, (0, self.back_radius))
+    }
+
+    /// Get the key points of the radius, which are used for the rings of the mesh
+    pub fn radius_key_points(&self, max_points: usize) -> Vec<R::ValueType> {
+        self.logic_radius.key_points(max_points)
+    }
+
+    /// Get the position of the point at the given angle and distance from the center in pixels
+    pub fn polar_to_backend(&self, angle: f64, distance: i32) -> BackendCoord {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        (
+            self.center.0 + (f64::from(distance) * sin).round() as i32,
+            self.center.1 - (f64::from(distance) * cos).round() as i32,
+        )
+    }
+}
+
+impl<R: Ranged> CoordTranslate for PolarCoord<R> {
+    type From = (f64, R::ValueType);
+
+    fn translate(&self, from: &Self::From) -> BackendCoord {
+        self.polar_to_backend(from.0, self.map_radius(&from.1))
+    }
+}
+
+impl<R: ReversableRanged> ReverseCoordTranslate for PolarCoord<R> {
+    fn reverse_translate(&self, input: BackendCoord) -> Option<Self::From> {
+        let dx = f64::from(input.0 - self.center.0);
+        let dy = f64::from(self.center.1 - input.1);
+
+        let distance = dx.hypot(dy).round() as i32;
+        let radius = self.logic_radius.unmap(distance, (0, self.back_radius))?;
+
+        let angle = dx.atan2(dy).to_degrees();
+        Some((if angle < 0.0 { angle + 360.0 } else { angle }, radius))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::coord::RangedCoordf64;
+
+    #[test]
+    fn test_polar_translate() {
+        let coord: PolarCoord<RangedCoordf64> = PolarCoord::new(0.0..10.0, (100, 100), 50);
+
+        assert_eq!(coord.translate(&(0.0, 10.0)), (100, 50));
+        assert_eq!(coord.translate(&(90.0, 5.0)), (125, 100));
+        assert_eq!(coord.translate(&(180.0, 10.0)), (100, 150));
+
+        let (angle, radius) = coord.reverse_translate((100, 120)).unwrap();
+        assert!((angle - 180.0).abs() < 1e-6);
+        assert!((radius - 4.0).abs() < 1e-6);
+        assert_eq!(coord.reverse_translate((0, 0)), None);
+    }
+}
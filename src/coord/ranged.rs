@@ -131,7 +131,7 @@ impl<'a, X: Ranged, Y: Ranged> MeshLine<'a, X, Y> {
             MeshLine::XMesh(a, b, _) => (a, b),
             MeshLine::YMesh(a, b, _) => (a, b),
         };
-        backend.draw_line(left, right, &Box::new(style.color))
+        backend.draw_line(left, right, style)
     }
 }
 
@@ -143,6 +143,13 @@ where
 {
     /// Get the smallest value that is larger than the `this` value
     fn next_value(this: &Self::ValueType) -> Self::ValueType;
+
+    /// Get the value where the band of the `this` value starts, which is where a histogram bar
+    /// of the value begins. Most coordinates map a value to the start of its band, so the value
+    /// is returned as it is.
+    fn band_start(this: Self::ValueType) -> Self::ValueType {
+        this
+    }
 }
 
 pub trait AsRangedCoord: Sized {
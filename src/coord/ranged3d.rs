@@ -0,0 +1,196 @@
+use super::{CoordTranslate, Ranged};
+use crate::drawing::backend::BackendCoord;
+
+use std::ops::Range;
+
+/// The resolution used when a ranged value is normalized to the unit cube
+const NORMALIZE_RESOLUTION: i32 = 1 << 20;
+
+/// The camera of a 3D coordinate system, which defines how the unit cube is projected to the
+/// screen. The projection is orthographic.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera {
+    /// The rotation around the vertical axis, in radians
+    pub yaw: f64,
+    /// The rotation around the horizontal axis after the yaw is applied, in radians. A positive
+    /// pitch looks at the chart from above
+    pub pitch: f64,
+    /// The size of the unit cube relative to the smaller side of the drawing area
+    pub scale: f64,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            yaw: 0.5,
+            pitch: 0.3,
+            scale: 0.6,
+        }
+    }
+}
+
+/// The 3D cartesian coordinate system described by three ranged values. The X axis goes right,
+/// the Y axis goes up and the Z axis goes away from the viewer when the camera isn't rotated.
+pub struct RangedCoord3D<X: Ranged, Y: Ranged, Z: Ranged> {
+    logic_x: X,
+    logic_y: Y,
+    logic_z: Z,
+    center: (f64, f64),
+    size: f64,
+    camera: Camera,
+}
+
+fn to_unit<R: Ranged>(logic: &R, value: &R::ValueType) -> f64 {
+    let mapped = logic.map(value, (0, NORMALIZE_RESOLUTION));
+    f64::from(mapped) / f64::from(NORMALIZE_RESOLUTION) - 0.5
+}
+
+impl<X: Ranged, Y: Ranged, Z: Ranged> RangedCoord3D<X, Y, Z> {
+    /// Create a new 3D coordinate system
+    /// - `logic_x`, `logic_y`, `logic_z`: The ranges of the three axes
+    /// - `actual`: The pixel range of the drawing area the coordinate is projected to
+    /// - `camera`: The camera used for the projection
+    pub fn new<IntoX: Into<X>, IntoY: Into<Y>, IntoZ: Into<Z>>(
+        logic_x: IntoX,
+        logic_y: IntoY,
+        logic_z: IntoZ,
+        actual: (Range<i32>, Range<i32>),
+        camera: Camera,
+    ) -> Self {
+        let (w, h) = (actual.0.end - actual.0.start, actual.1.end - actual.1.start);
+        Self {
+            logic_x: logic_x.into(),
+            logic_y: logic_y.into(),
+            logic_z: logic_z.into(),
+            center: (
+                f64::from(actual.0.start + actual.0.end) / 2.0,
+                f64::from(actual.1.start + actual.1.end) / 2.0,
+            ),
+            size: f64::from(w.min(h)),
+            camera,
+        }
+    }
+
+    /// Get the camera of the coordinate
+    pub fn get_camera(&self) -> Camera {
+        self.camera
+    }
+
+    /// Change the camera of the coordinate
+    pub fn set_camera(&mut self, camera: Camera) {
+        self.camera = camera;
+    }
+
+    /// Get the range of X axis
+    pub fn get_x_range(&self) -> Range<X::ValueType> {
+        self.logic_x.range()
+    }
+
+    /// Get the range of Y axis
+    pub fn get_y_range(&self) -> Range<Y::ValueType> {
+        self.logic_y.range()
+    }
+
+    /// Get the range of Z axis
+    pub fn get_z_range(&self) -> Range<Z::ValueType> {
+        self.logic_z.range()
+    }
+
+    /// Get the key points of the three axes
+    #[allow(clippy::type_complexity)]
+    pub fn key_points(
+        &self,
+        (x_max, y_max, z_max): (usize, usize, usize),
+    ) -> (Vec<X::ValueType>, Vec<Y::ValueType>, Vec<Z::ValueType>) {
+        (
+            self.logic_x.key_points(x_max),
+            self.logic_y.key_points(y_max),
+            self.logic_z.key_points(z_max),
+        )
+    }
+
+    /// Map the values to the unit cube, which is centered at the origin
+    pub fn normalize(
+        &self,
+        (x, y, z): (&X::ValueType, &Y::ValueType, &Z::ValueType),
+    ) -> (f64, f64, f64) {
+        (
+            self.normalize_x(x),
+            self.normalize_y(y),
+            self.normalize_z(z),
+        )
+    }
+
+    /// Map a X value to the range of the unit cube
+    pub fn normalize_x(&self, x: &X::ValueType) -> f64 {
+        to_unit(&self.logic_x, x)
+    }
+
+    /// Map a Y value to the range of the unit cube
+    pub fn normalize_y(&self, y: &Y::ValueType) -> f64 {
+        to_unit(&self.logic_y, y)
+    }
+
+    /// Map a Z value to the range of the unit cube
+    pub fn normalize_z(&self, z: &Z::ValueType) -> f64 {
+        to_unit(&self.logic_z, z)
+    }
+
+    /// Rotate a point in the unit cube space by the camera. The result is the horizontal and
+    /// vertical position on the screen, plus the depth, which is larger when the point is farther
+    /// away from the viewer.
+    pub fn rotate(&self, (x, y, z): (f64, f64, f64)) -> (f64, f64, f64) {
+        let (sin_yaw, cos_yaw) = self.camera.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.camera.pitch.sin_cos();
+
+        let (x, z) = (x * cos_yaw - z * sin_yaw, x * sin_yaw + z * cos_yaw);
+        let (y, z) = (y * cos_pitch + z * sin_pitch, z * cos_pitch - y * sin_pitch);
+
+        (x, y, z)
+    }
+
+    /// Project a point in the unit cube space to the backend coordinate
+    pub fn project(&self, point: (f64, f64, f64)) -> BackendCoord {
+        let (x, y, _) = self.rotate(point);
+        let scale = self.size * self.camera.scale;
+        (
+            (self.center.0 + x * scale).round() as i32,
+            (self.center.1 - y * scale).round() as i32,
+        )
+    }
+}
+
+impl<X: Ranged, Y: Ranged, Z: Ranged> CoordTranslate for RangedCoord3D<X, Y, Z> {
+    type From = (X::ValueType, Y::ValueType, Z::ValueType);
+
+    fn translate(&self, from: &Self::From) -> BackendCoord {
+        self.project(self.normalize((&from.0, &from.1, &from.2)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::coord::RangedCoordf64;
+
+    #[test]
+    fn test_projection() {
+        let camera = Camera {
+            yaw: 0.0,
+            pitch: 0.0,
+            scale: 1.0,
+        };
+        let mut coord: RangedCoord3D<RangedCoordf64, RangedCoordf64, RangedCoordf64> =
+            RangedCoord3D::new(0.0..1.0, 0.0..1.0, 0.0..1.0, (0..100, 0..100), camera);
+
+        assert_eq!(coord.translate(&(0.0, 0.0, 0.0)), (0, 100));
+        assert_eq!(coord.translate(&(1.0, 1.0, 0.5)), (100, 0));
+
+        coord.set_camera(Camera {
+            yaw: std::f64::consts::FRAC_PI_2,
+            ..camera
+        });
+        assert_eq!(coord.translate(&(0.5, 0.5, 0.0)), (100, 50));
+        assert!(coord.rotate((-0.5, 0.0, 0.0)).2 < 0.0);
+    }
+}
@@ -1,8 +1,9 @@
 /// The abstraction of a drawing area
 use super::backend::{BackendCoord, DrawingBackend, DrawingErrorKind};
+use super::clipping::ClippedBackend;
 use crate::coord::{CoordTranslate, MeshLine, Ranged, RangedCoord, Shift};
 use crate::element::{Drawable, PointCollection};
-use crate::style::{Color, TextStyle};
+use crate::style::{Color, HAnchor, TextLayout, TextStyle, VAnchor};
 
 use std::borrow::Borrow;
 use std::cell::RefCell;
@@ -63,11 +64,6 @@ impl Rect {
                 x1: compute_evenly_split(self.x0, self.x1, col, ci + 1),
             })
     }
-
-    /// Make the coordinate in the range of the rectangle
-    fn truncate(&self, p: (i32, i32)) -> (i32, i32) {
-        (p.0.min(self.x1).max(self.x0), p.1.min(self.y1).max(self.y0))
-    }
 }
 
 /// The abstraction of a region
@@ -235,17 +231,26 @@ impl<DB: DrawingBackend, CT: CoordTranslate> DrawingArea<DB, CT> {
         self.backend_ops(|b| b.present())
     }
 
+    /// Present all the pending changes to the backend and consume the drawing area. The file
+    /// backends save the image when they are dropped otherwise, which can't report an error.
+    pub fn into_result(self) -> Result<(), DrawingAreaError<DB>> {
+        self.present()
+    }
+
     /// Draw an high-level element
     pub fn draw<'a, E>(&self, element: &'a E) -> Result<(), DrawingAreaError<DB>>
     where
         &'a E: PointCollection<'a, CT::From>,
         E: Drawable,
     {
-        let backend_coords = element.point_iter().into_iter().map(|p| {
-            let b = p.borrow();
-            self.rect.truncate(self.coord.translate(b))
-        });
-        self.backend_ops(move |b| element.draw(backend_coords, b))
+        let backend_coords = element
+            .point_iter()
+            .into_iter()
+            .map(|p| self.coord.translate(p.borrow()));
+        let clip = ((self.rect.x0, self.rect.y0), (self.rect.x1, self.rect.y1));
+        self.backend_ops(move |b| {
+            element.draw(backend_coords, &mut ClippedBackend::new(b, clip.0, clip.1))
+        })
     }
 
     /// Map coordinate to the backend coordinate
@@ -347,17 +352,18 @@ impl<DB: DrawingBackend> DrawingArea<DB, Shift> {
                 ));
             }
         };
-        let padding = if self.rect.x1 - self.rect.x0 > text_w as i32 {
-            (self.rect.x1 - self.rect.x0 - text_w as i32) / 2
+        let x = if self.rect.x1 - self.rect.x0 > text_w as i32 {
+            (self.rect.x0 + self.rect.x1) / 2
         } else {
-            0
+            self.rect.x0 + text_w as i32 / 2
         };
 
         self.backend_ops(|b| {
             b.draw_text(
                 text,
                 style.font,
-                (self.rect.x0 + padding, self.rect.y0 + 5),
+                (x, self.rect.y0 + 5),
+                &TextLayout::new(HAnchor::Center, VAnchor::Top),
                 &Box::new(style.color),
             )
         })?;
@@ -375,6 +381,8 @@ impl<DB: DrawingBackend> DrawingArea<DB, Shift> {
     }
 
     /// Draw text on the drawing area
+    /// - `pos`: The position of the text, which is the upper left corner unless the text style
+    ///   has another layout
     pub fn draw_text(
         &self,
         text: &str,
@@ -386,6 +394,7 @@ impl<DB: DrawingBackend> DrawingArea<DB, Shift> {
                 text,
                 style.font,
                 (pos.0 + self.rect.x0, pos.1 + self.rect.y0),
+                &style.layout,
                 &Box::new(style.color),
             )
         })
@@ -396,4 +405,14 @@ impl<DB: DrawingBackend, CT: CoordTranslate> DrawingArea<DB, CT> {
     pub fn into_coord_spec(self) -> CT {
         self.coord
     }
+
+    /// Get a reference to the coordinate specification of this drawing area
+    pub fn as_coord_spec(&self) -> &CT {
+        &self.coord
+    }
+
+    /// Get a mutable reference to the coordinate specification of this drawing area
+    pub fn as_coord_spec_mut(&mut self) -> &mut CT {
+        &mut self.coord
+    }
 }
@@ -1,4 +1,7 @@
-use crate::style::{Color, FontDesc, FontError, Mixable};
+use super::rasterizer;
+use crate::style::{
+    Color, FillRule, FontDesc, FontError, LineCap, LineJoin, Mixable, ShapeStyle, TextLayout,
+};
 use std::error::Error;
 
 /// A coordiante in the image
@@ -31,7 +34,32 @@ pub trait BackendStyle {
 
     /// Convert the style into the underlying color
     fn as_color(&self) -> &Self::ColorType;
-    // TODO: In the future we should support stroke width, line shape, etc....
+
+    /// The width of the stroke in pixels
+    fn stroke_width(&self) -> u32 {
+        1
+    }
+
+    /// The dash pattern of the stroke in pixels, alternating between the drawn and skipped
+    /// length. An empty pattern means a solid stroke.
+    fn stroke_dash(&self) -> &[u32] {
+        &[]
+    }
+
+    /// How the end points of an open stroke are drawn
+    fn line_cap(&self) -> LineCap {
+        LineCap::Butt
+    }
+
+    /// How the connected segments of a stroke are joined
+    fn line_join(&self) -> LineJoin {
+        LineJoin::Miter
+    }
+
+    /// Which part of a self-intersecting polygon is filled
+    fn fill_rule(&self) -> FillRule {
+        FillRule::NonZero
+    }
 }
 
 impl<T: Color> BackendStyle for T {
@@ -41,6 +69,28 @@ impl<T: Color> BackendStyle for T {
     }
 }
 
+impl<'a> BackendStyle for ShapeStyle<'a> {
+    type ColorType = &'a dyn Color;
+    fn as_color(&self) -> &Self::ColorType {
+        &self.color
+    }
+    fn stroke_width(&self) -> u32 {
+        self.stroke_width
+    }
+    fn stroke_dash(&self) -> &[u32] {
+        self.dash_pattern
+    }
+    fn line_cap(&self) -> LineCap {
+        self.line_cap
+    }
+    fn line_join(&self) -> LineJoin {
+        self.line_join
+    }
+    fn fill_rule(&self) -> FillRule {
+        self.fill_rule
+    }
+}
+
 /// The drawing context
 pub trait DrawingBackend {
     /// The error reported by the backend
@@ -59,6 +109,18 @@ pub trait DrawingBackend {
     /// pending changes on the screen.
     fn present(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>>;
 
+    /// Restrict the following drawing operations to the rectangle given by its upper-left and
+    /// bottom-right corners, `None` removes the restriction.
+    /// This is used to clip the shapes that can't be clipped geometrically, such as circles and
+    /// text. The return value indicates if the backend is able to clip natively, otherwise the
+    /// shape is rasterized and clipped pixel by pixel.
+    fn set_clip_region(
+        &mut self,
+        _region: Option<(BackendCoord, BackendCoord)>,
+    ) -> Result<bool, DrawingErrorKind<Self::ErrorType>> {
+        Ok(false)
+    }
+
     /// Draw a pixel
     fn draw_pixel<S: Color>(
         &mut self,
@@ -73,6 +135,10 @@ pub trait DrawingBackend {
         mut to: BackendCoord,
         style: &S,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        if !rasterizer::is_hairline(style) {
+            return rasterizer::stroke_path(self, &[from, to], false, style);
+        }
+
         let steep = (from.0 - to.0).abs() < (from.1 - to.1).abs();
 
         if steep {
@@ -130,13 +196,25 @@ pub trait DrawingBackend {
         if fill {
             if bottom_right.0 - upper_left.0 < bottom_right.1 - upper_left.1 {
                 for x in upper_left.0..=bottom_right.0 {
-                    self.draw_line((x, upper_left.1), (x, bottom_right.1), style)?;
+                    self.draw_line((x, upper_left.1), (x, bottom_right.1), style.as_color())?;
                 }
             } else {
                 for y in upper_left.1..=bottom_right.1 {
-                    self.draw_line((upper_left.0, y), (bottom_right.0, y), style)?;
+                    self.draw_line((upper_left.0, y), (bottom_right.0, y), style.as_color())?;
                 }
             }
+        } else if !rasterizer::is_hairline(style) {
+            rasterizer::stroke_path(
+                self,
+                &[
+                    upper_left,
+                    (bottom_right.0, upper_left.1),
+                    bottom_right,
+                    (upper_left.0, bottom_right.1),
+                ],
+                true,
+                style,
+            )?;
         } else {
             self.draw_line(
                 (upper_left.0, upper_left.1),
@@ -168,6 +246,11 @@ pub trait DrawingBackend {
         path: I,
         style: &S,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        if !rasterizer::is_hairline(style) {
+            let path: Vec<_> = path.into_iter().collect();
+            return rasterizer::stroke_path(self, &path, false, style);
+        }
+
         let mut begin: Option<BackendCoord> = None;
         for end in path.into_iter() {
             if let Some(begin) = begin {
@@ -178,6 +261,24 @@ pub trait DrawingBackend {
         Ok(())
     }
 
+    /// Draw a polygon, the last vertex is connected to the first one
+    fn draw_polygon<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        vert: I,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let vert: Vec<_> = vert.into_iter().collect();
+        if !fill {
+            return rasterizer::stroke_path(self, &vert, true, style);
+        }
+        let vert: Vec<_> = vert
+            .into_iter()
+            .map(|(x, y)| (f64::from(x), f64::from(y)))
+            .collect();
+        rasterizer::fill_polygon(self, &vert, style.fill_rule(), style.as_color())
+    }
+
     /// Draw a circle
     fn draw_circle<S: BackendStyle>(
         &mut self,
@@ -186,68 +287,21 @@ pub trait DrawingBackend {
         style: &S,
         fill: bool,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        //let range = ((radius + 3) / 4) as i32..=(2 * radius - radius / 4) as i32;
-        let min = (f64::from(radius) * (1.0 - (2f64).sqrt() / 2.0)).ceil() as i32;
-        let max = (f64::from(radius) * (1.0 + (2f64).sqrt() / 2.0)).floor() as i32;
-
-        let range = min..=max;
-
-        let (up, down) = (
-            range.start() + center.1 - radius as i32,
-            range.end() + center.1 - radius as i32,
-        );
-
-        for dy in range {
-            let dy = dy - radius as i32;
-            let y = center.1 + dy;
-
-            let lx = (f64::from(radius) * f64::from(radius)
-                - (f64::from(dy) * f64::from(dy)).max(1e-5))
-            .sqrt();
-
-            let left = center.0 - lx.floor() as i32;
-            let right = center.0 + lx.floor() as i32;
-
-            let v = lx - lx.floor();
-
-            let x = center.0 + dy;
-            let top = center.1 - lx.floor() as i32;
-            let bottom = center.1 + lx.floor() as i32;
-
-            if fill {
-                self.draw_line((left, y), (right, y), style)?;
-                self.draw_line((x, top), (x, up), style)?;
-                self.draw_line((x, down), (x, bottom), style)?;
-            } else {
-                self.draw_pixel((left, y), &style.as_color().mix(1.0 - v))?;
-                self.draw_pixel((right, y), &style.as_color().mix(1.0 - v))?;
-
-                self.draw_pixel((x, top), &style.as_color().mix(1.0 - v))?;
-                self.draw_pixel((x, bottom), &style.as_color().mix(1.0 - v))?;
-            }
-
-            self.draw_pixel((left - 1, y), &style.as_color().mix(v))?;
-            self.draw_pixel((right + 1, y), &style.as_color().mix(v))?;
-            self.draw_pixel((x, top - 1), &style.as_color().mix(v))?;
-            self.draw_pixel((x, bottom + 1), &style.as_color().mix(v))?;
-        }
-
-        Ok(())
+        rasterizer::draw_circle(self, center, radius, style, fill)
     }
 
     /// Draw a text
+    /// - `pos`: The position of the anchor point of the text
+    /// - `layout`: Which point of the text is the anchor point, and how the text is rotated
+    ///   around it
     fn draw_text<'a, C: Color>(
         &mut self,
         text: &str,
         font: &FontDesc<'a>,
         pos: BackendCoord,
+        layout: &TextLayout,
         color: &C,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        match font.draw(text, (pos.0, pos.1), |x, y, v| {
-            self.draw_pixel((x as i32, y as i32), &color.mix(f64::from(v)))
-        }) {
-            Ok(drawing_result) => drawing_result,
-            Err(font_error) => Err(DrawingErrorKind::FontError(font_error)),
-        }
+        rasterizer::draw_text(self, text, font, pos, layout, color)
     }
 }
@@ -1,35 +1,271 @@
+use super::PresentOnDrop;
 use crate::drawing::backend::{BackendCoord, DrawingBackend, DrawingErrorKind};
 use crate::style::Color;
-use image::{ImageError, Rgb, RgbImage};
+use image::{ColorType, DynamicImage, ImageError, ImageOutputFormat, RgbImage, RgbaImage};
 
+use std::io::Write;
 use std::path::Path;
 
+/// The layout of the pixels in the buffer of a bitmap backend
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PixelFormat {
+    /// Three bytes per pixel, in red, green, blue order
+    Rgb,
+    /// Four bytes per pixel, in red, green, blue, alpha order
+    Rgba,
+}
+
+impl PixelFormat {
+    /// The number of bytes each pixel takes
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            PixelFormat::Rgb => 3,
+            PixelFormat::Rgba => 4,
+        }
+    }
+
+    fn color_type(self) -> ColorType {
+        match self {
+            PixelFormat::Rgb => ColorType::RGB(8),
+            PixelFormat::Rgba => ColorType::RGBA(8),
+        }
+    }
+}
+
+/// The pixels of the bitmap, which are either owned by the backend or borrowed from the caller
+enum Pixels<'a> {
+    Owned(Vec<u8>),
+    Borrowed(&'a mut [u8]),
+}
+
+impl Pixels<'_> {
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            Pixels::Owned(pixels) => pixels,
+            Pixels::Borrowed(pixels) => pixels,
+        }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        match self {
+            Pixels::Owned(pixels) => pixels,
+            Pixels::Borrowed(pixels) => pixels,
+        }
+    }
+}
+
+/// What happens to the bitmap when the backend is presented
+enum Target<'a> {
+    /// Save the image to a file, the image format is derived from the file extension
+    File(&'a Path),
+    /// Encode the image to a writer in the given image format
+    Writer(Box<dyn Write + 'a>, ImageOutputFormat),
+    /// The caller owns the pixel buffer, so the pixels are already in place
+    Buffer,
+    /// Append a frame to an animated GIF, the frame delay is in hundredths of a second
+    #[cfg(feature = "gif_backend")]
+    Gif(Box<gif::Encoder<Box<dyn Write + 'a>>>, u16),
+}
+
 /// The backend that drawing a bitmap
 pub struct BitMapBackend<'a> {
-    /// The path to the image
-    path: &'a Path,
-    /// The image object
-    img: RgbImage,
-    /// Flag indicates if the bitmap has been saved
+    /// Where the bitmap goes when it's presented
+    target: Target<'a>,
+    /// The pixels of the image
+    pixels: Pixels<'a>,
+    /// The layout of the pixels
+    format: PixelFormat,
+    /// The size of the image
+    size: (u32, u32),
+    /// Flag indicates if the latest drawing has been presented
     saved: bool,
 }
 
 impl<'a> BitMapBackend<'a> {
+    fn with_target(target: Target<'a>, dimension: (u32, u32), format: PixelFormat) -> Self {
+        let len = dimension.0 as usize * dimension.1 as usize * format.bytes_per_pixel();
+        Self {
+            target,
+            pixels: Pixels::Owned(vec![0; len]),
+            format,
+            size: dimension,
+            saved: false,
+        }
+    }
+
     /// Create a new bitmap backend
     pub fn new<T: AsRef<Path> + ?Sized>(path: &'a T, dimension: (u32, u32)) -> Self {
+        Self::with_target(Target::File(path.as_ref()), dimension, PixelFormat::Rgb)
+    }
+
+    /// Create a new bitmap backend with an alpha channel, whose background is transparent.
+    /// The image format must support the alpha channel, for example PNG.
+    /// - `path`: The path of the image file
+    /// - `dimension`: The size of the image
+    pub fn new_rgba<T: AsRef<Path> + ?Sized>(path: &'a T, dimension: (u32, u32)) -> Self {
+        Self::with_target(Target::File(path.as_ref()), dimension, PixelFormat::Rgba)
+    }
+
+    /// Create a new bitmap backend which encodes the image to a writer when it's presented,
+    /// without touching the file system
+    /// - `writer`: The writer the encoded image is written to
+    /// - `dimension`: The size of the image
+    /// - `format`: The image format used for encoding, for example `image::ImageFormat::PNG`
+    pub fn with_writer<W: Write + 'a, F: Into<ImageOutputFormat>>(
+        writer: W,
+        dimension: (u32, u32),
+        format: F,
+    ) -> Self {
+        Self::with_target(
+            Target::Writer(Box::new(writer), format.into()),
+            dimension,
+            PixelFormat::Rgb,
+        )
+    }
+
+    /// Create a new bitmap backend with an alpha channel, whose background is transparent, which
+    /// encodes the image to a writer when it's presented
+    /// - `writer`: The writer the encoded image is written to
+    /// - `dimension`: The size of the image
+    /// - `format`: The image format used for encoding, which must support the alpha channel,
+    ///   for example `image::ImageFormat::PNG`
+    pub fn with_writer_rgba<W: Write + 'a, F: Into<ImageOutputFormat>>(
+        writer: W,
+        dimension: (u32, u32),
+        format: F,
+    ) -> Self {
+        Self::with_target(
+            Target::Writer(Box::new(writer), format.into()),
+            dimension,
+            PixelFormat::Rgba,
+        )
+    }
+
+    /// Create a new bitmap backend which draws into a buffer owned by the caller. The buffer
+    /// has the drawing once the backend is presented or dropped.
+    /// - `buffer`: The pixel buffer, whose rows are stored from top to bottom without padding
+    /// - `dimension`: The size of the image
+    /// - `format`: The layout of the pixels in the buffer. In the RGBA format, the colors are
+    ///   composited over the pixels in the buffer with their alpha channel
+    /// - Returns: The backend, or `ImageError::DimensionError` if the buffer is too small for
+    ///   the image
+    pub fn with_buffer(
+        buffer: &'a mut [u8],
+        dimension: (u32, u32),
+        format: PixelFormat,
+    ) -> Result<Self, ImageError> {
+        let len = dimension.0 as usize * dimension.1 as usize * format.bytes_per_pixel();
+        if buffer.len() < len {
+            return Err(ImageError::DimensionError);
+        }
+        Ok(Self {
+            target: Target::Buffer,
+            pixels: Pixels::Borrowed(&mut buffer[..len]),
+            format,
+            size: dimension,
+            saved: false,
+        })
+    }
+
+    /// Create a new bitmap backend which draws into an image owned by the caller
+    /// - `image`: The image to draw on
+    pub fn with_image(image: &'a mut RgbImage) -> Self {
+        let size = image.dimensions();
         Self {
-            path: path.as_ref(),
-            img: RgbImage::new(dimension.0, dimension.1),
+            target: Target::Buffer,
+            pixels: Pixels::Borrowed(image),
+            format: PixelFormat::Rgb,
+            size,
             saved: false,
         }
     }
+
+    /// Create a new bitmap backend which generates an animated GIF. Each time the backend is
+    /// presented, the image is appended to the animation as a new frame.
+    /// - `path`: The path of the GIF file
+    /// - `dimension`: The size of the image
+    /// - `frame_delay`: How long each frame is shown, in milliseconds
+    /// - `loop_count`: How many times the animation is repeated, 0 repeats it forever
+    #[cfg(feature = "gif_backend")]
+    pub fn gif<T: AsRef<Path> + ?Sized>(
+        path: &T,
+        dimension: (u32, u32),
+        frame_delay: u32,
+        loop_count: u16,
+    ) -> Result<Self, ImageError> {
+        let file = std::fs::File::create(path.as_ref()).map_err(ImageError::IoError)?;
+        Self::gif_with_writer(file, dimension, frame_delay, loop_count)
+    }
+
+    /// Create a new bitmap backend which writes an animated GIF to a writer. Each time the
+    /// backend is presented, the image is appended to the animation as a new frame.
+    /// - `writer`: The writer the animation is written to
+    /// - `dimension`: The size of the image
+    /// - `frame_delay`: How long each frame is shown, in milliseconds
+    /// - `loop_count`: How many times the animation is repeated, 0 repeats it forever
+    #[cfg(feature = "gif_backend")]
+    pub fn gif_with_writer<W: Write + 'a>(
+        writer: W,
+        dimension: (u32, u32),
+        frame_delay: u32,
+        loop_count: u16,
+    ) -> Result<Self, ImageError> {
+        use gif::{Encoder, Repeat, SetParameter};
+
+        if dimension.0 > u32::from(u16::MAX) || dimension.1 > u32::from(u16::MAX) {
+            return Err(ImageError::DimensionError);
+        }
+        let writer: Box<dyn Write + 'a> = Box::new(writer);
+        let mut encoder = Encoder::new(writer, dimension.0 as u16, dimension.1 as u16, &[])?;
+        encoder.set(if loop_count == 0 {
+            Repeat::Infinite
+        } else {
+            Repeat::Finite(loop_count)
+        })?;
+
+        let delay = (frame_delay / 10).min(u32::from(u16::MAX)) as u16;
+        Ok(Self::with_target(
+            Target::Gif(Box::new(encoder), delay),
+            dimension,
+            PixelFormat::Rgb,
+        ))
+    }
+
+    /// Get the layout of the pixels in the buffer of this backend
+    pub fn pixel_format(&self) -> PixelFormat {
+        self.format
+    }
+
+    /// Save the image and consume the backend
+    pub fn finish(mut self) -> Result<(), DrawingErrorKind<ImageError>> {
+        self.finish_present()
+    }
+}
+
+/// Encode the pixels to a writer in the given image format
+fn encode<W: Write>(
+    pixels: &[u8],
+    (w, h): (u32, u32),
+    format: PixelFormat,
+    writer: &mut W,
+    output_format: ImageOutputFormat,
+) -> Result<(), ImageError> {
+    let pixels = pixels.to_vec();
+    let image = match format {
+        PixelFormat::Rgb => RgbImage::from_raw(w, h, pixels).map(DynamicImage::ImageRgb8),
+        PixelFormat::Rgba => RgbaImage::from_raw(w, h, pixels).map(DynamicImage::ImageRgba8),
+    };
+    match image {
+        Some(image) => image.write_to(writer, output_format),
+        None => Err(ImageError::DimensionError),
+    }
 }
 
 impl<'a> DrawingBackend for BitMapBackend<'a> {
     type ErrorType = ImageError;
 
     fn get_size(&self) -> (u32, u32) {
-        (self.img.width(), self.img.height())
+        self.size
     }
 
     fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<ImageError>> {
@@ -37,9 +273,36 @@ impl<'a> DrawingBackend for BitMapBackend<'a> {
     }
 
     fn present(&mut self) -> Result<(), DrawingErrorKind<ImageError>> {
-        self.img
-            .save(&self.path)
-            .map_err(|x| DrawingErrorKind::DrawingError(ImageError::IoError(x)))?;
+        match self.target {
+            Target::File(path) => image::save_buffer(
+                path,
+                self.pixels.as_slice(),
+                self.size.0,
+                self.size.1,
+                self.format.color_type(),
+            )
+            .map_err(|x| DrawingErrorKind::DrawingError(ImageError::IoError(x)))?,
+            Target::Writer(ref mut writer, ref output_format) => encode(
+                self.pixels.as_slice(),
+                self.size,
+                self.format,
+                writer,
+                output_format.clone(),
+            )
+            .and_then(|_| writer.flush().map_err(ImageError::IoError))
+            .map_err(DrawingErrorKind::DrawingError)?,
+            Target::Buffer => {}
+            #[cfg(feature = "gif_backend")]
+            Target::Gif(ref mut encoder, delay) => {
+                let (w, h) = (self.size.0 as u16, self.size.1 as u16);
+                // A speed of 10 is the balance of speed and quality suggested by the encoder
+                let mut frame = gif::Frame::from_rgb_speed(w, h, self.pixels.as_slice(), 10);
+                frame.delay = delay;
+                encoder
+                    .write_frame(&frame)
+                    .map_err(|x| DrawingErrorKind::DrawingError(ImageError::IoError(x)))?;
+            }
+        }
         self.saved = true;
         Ok(())
     }
@@ -49,47 +312,178 @@ impl<'a> DrawingBackend for BitMapBackend<'a> {
         point: BackendCoord,
         color: &C,
     ) -> Result<(), DrawingErrorKind<ImageError>> {
-        if point.0 as u32 >= self.img.width()
+        if point.0 as u32 >= self.size.0
             || point.0 < 0
-            || point.1 as u32 >= self.img.height()
+            || point.1 as u32 >= self.size.1
             || point.1 < 0
         {
             return Ok(());
         }
+        self.saved = false;
 
         let alpha = color.alpha();
         let rgb = color.rgb();
+        let new_color = [rgb.0, rgb.1, rgb.2];
 
-        if alpha >= 1.0 {
-            self.img.put_pixel(
-                point.0 as u32,
-                point.1 as u32,
-                Rgb {
-                    data: [rgb.0, rgb.1, rgb.2],
-                },
-            );
-        } else {
-            let pixel = self.img.get_pixel_mut(point.0 as u32, point.1 as u32);
+        let bytes_per_pixel = self.format.bytes_per_pixel();
+        let offset = (point.1 as usize * self.size.0 as usize + point.0 as usize) * bytes_per_pixel;
+        let pixel = &mut self.pixels.as_mut_slice()[offset..offset + bytes_per_pixel];
 
-            let new_color = [rgb.0, rgb.1, rgb.2];
+        if alpha >= 1.0 {
+            pixel[..3].copy_from_slice(&new_color);
+            if self.format == PixelFormat::Rgba {
+                pixel[3] = 255;
+            }
+            return Ok(());
+        }
+        if alpha <= 0.0 {
+            return Ok(());
+        }
 
-            pixel
-                .data
-                .iter_mut()
-                .zip(&new_color)
-                .for_each(|(old, new)| {
-                    *old = (f64::from(*old) * (1.0 - alpha) + f64::from(*new) * alpha).min(255.0)
-                        as u8;
-                });
+        // Composite the color over the pixel with the source-over operator, the pixels without
+        // an alpha channel are opaque
+        let old_alpha = match self.format {
+            PixelFormat::Rgb => 1.0,
+            PixelFormat::Rgba => f64::from(pixel[3]) / 255.0,
+        };
+        let new_alpha = alpha + old_alpha * (1.0 - alpha);
+        pixel.iter_mut().zip(&new_color).for_each(|(old, new)| {
+            let blended = f64::from(*new) * alpha + f64::from(*old) * old_alpha * (1.0 - alpha);
+            *old = (blended / new_alpha).min(255.0) as u8;
+        });
+        if self.format == PixelFormat::Rgba {
+            pixel[3] = (new_alpha * 255.0).round() as u8;
         }
         Ok(())
     }
 }
 
+impl PresentOnDrop for BitMapBackend<'_> {
+    fn is_saved(&self) -> bool {
+        self.saved
+    }
+
+    fn set_saved(&mut self) {
+        self.saved = true;
+    }
+}
+
 impl Drop for BitMapBackend<'_> {
     fn drop(&mut self) {
-        if !self.saved {
-            self.present().expect("Unable to save the bitmap");
+        self.present_on_drop();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::drawing::take_drop_error;
+    use crate::style::RGBColor;
+
+    #[test]
+    fn test_draw_into_buffer() {
+        let mut buffer = vec![0u8; 4 * 3 * 2];
+        {
+            let mut backend =
+                BitMapBackend::with_buffer(&mut buffer, (3, 2), PixelFormat::Rgba).unwrap();
+            backend.draw_pixel((1, 1), &RGBColor(10, 20, 30)).unwrap();
+            backend.draw_pixel((3, 0), &RGBColor(10, 20, 30)).unwrap();
         }
+        assert_eq!(&buffer[16..20], &[10, 20, 30, 255]);
+        assert_eq!(buffer.iter().filter(|&&b| b != 0).count(), 4);
+        assert!(BitMapBackend::with_buffer(&mut buffer, (3, 2), PixelFormat::Rgb).is_ok());
+        assert!(matches!(
+            BitMapBackend::with_buffer(&mut buffer, (4, 2), PixelFormat::Rgba),
+            Err(ImageError::DimensionError)
+        ));
+
+        let mut image = RgbImage::new(3, 2);
+        {
+            let mut backend = BitMapBackend::with_image(&mut image);
+            backend.draw_pixel((2, 0), &RGBColor(1, 2, 3)).unwrap();
+        }
+        assert_eq!(image.get_pixel(2, 0).data, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_encode_to_writer() {
+        let mut png = vec![];
+        {
+            let mut backend = BitMapBackend::with_writer(&mut png, (4, 4), image::ImageFormat::PNG);
+            backend.draw_pixel((0, 0), &RGBColor(255, 0, 0)).unwrap();
+            backend.present().unwrap();
+        }
+        assert_eq!(&png[1..4], b"PNG");
+
+        let decoded = image::load_from_memory(&png).unwrap().to_rgb();
+        assert_eq!(decoded.get_pixel(0, 0).data, [255, 0, 0]);
+    }
+
+    #[test]
+    fn test_transparent_background() {
+        use crate::style::{Mixable, Transparent};
+
+        let mut png = vec![];
+        {
+            let mut backend =
+                BitMapBackend::with_writer_rgba(&mut png, (4, 1), image::ImageFormat::PNG);
+            let half_blue = RGBColor(0, 0, 255).mix(0.5);
+            for x in 0..4 {
+                backend.draw_pixel((x, 0), &Transparent).unwrap();
+            }
+            backend.draw_pixel((0, 0), &RGBColor(255, 0, 0)).unwrap();
+            backend.draw_pixel((1, 0), &half_blue).unwrap();
+            backend.draw_pixel((2, 0), &RGBColor(255, 0, 0)).unwrap();
+            backend.draw_pixel((2, 0), &half_blue).unwrap();
+            backend.draw_pixel((3, 0), &half_blue).unwrap();
+            backend.draw_pixel((3, 0), &half_blue).unwrap();
+        }
+
+        let decoded = image::load_from_memory(&png).unwrap().to_rgba();
+        assert_eq!(decoded.get_pixel(0, 0).data, [255, 0, 0, 255]);
+        assert_eq!(decoded.get_pixel(1, 0).data, [0, 0, 255, 128]);
+        assert_eq!(decoded.get_pixel(2, 0).data, [127, 0, 127, 255]);
+        assert_eq!(decoded.get_pixel(3, 0).data, [0, 0, 255, 192]);
+    }
+
+    #[test]
+    fn test_finish_error() {
+        let path = "/nonexistent-directory/plotters-test.png";
+        assert!(BitMapBackend::new(path, (4, 4)).finish().is_err());
+        assert!(take_drop_error().is_none());
+        // Dropping a backend which can't be saved must not panic, the error is recorded instead
+        drop(BitMapBackend::new(path, (4, 4)));
+        assert!(take_drop_error().is_some());
+        assert!(take_drop_error().is_none());
+
+        let mut png = vec![];
+        BitMapBackend::with_writer(&mut png, (4, 4), image::ImageFormat::PNG)
+            .finish()
+            .unwrap();
+        assert!(!png.is_empty());
+    }
+
+    #[cfg(feature = "gif_backend")]
+    #[test]
+    fn test_animated_gif() {
+        use image::AnimationDecoder;
+
+        let mut output = vec![];
+        {
+            let mut backend = BitMapBackend::gif_with_writer(&mut output, (4, 4), 200, 0).unwrap();
+            for x in 0..3 {
+                backend.draw_pixel((x, 0), &RGBColor(255, 0, 0)).unwrap();
+                backend.present().unwrap();
+            }
+            // The frame drawn after the last present is added when the backend is dropped
+            backend.draw_pixel((3, 0), &RGBColor(255, 0, 0)).unwrap();
+        }
+
+        let decoder = image::gif::Decoder::new(&output[..]).unwrap();
+        let frames = decoder.into_frames().collect_frames().unwrap();
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[0].delay().to_integer(), 200);
+        assert_eq!(frames[3].buffer().get_pixel(3, 0).data, [255, 0, 0, 255]);
+        assert!(output.windows(11).any(|w| w == b"NETSCAPE2.0"));
     }
 }
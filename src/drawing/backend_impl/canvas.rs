@@ -1,15 +1,17 @@
-use js_sys::JSON;
+use js_sys::{Array, JSON};
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{window, CanvasRenderingContext2d, HtmlCanvasElement};
 
 use crate::drawing::backend::{BackendCoord, BackendStyle, DrawingBackend, DrawingErrorKind};
-use crate::style::{Color, FontDesc};
+use crate::style::{Color, FontDesc, HAnchor, LineCap, LineJoin, TextLayout, VAnchor};
 
 /// The backend that is drawing on the HTML canvas
 /// TODO: Support double bufferring
 pub struct CanvasBackend {
     canvas: HtmlCanvasElement,
     context: CanvasRenderingContext2d,
+    /// Flag indicates if a clip region is active, which is a saved context state
+    clipped: bool,
 }
 
 pub struct CanvasError(JsValue);
@@ -49,7 +51,11 @@ impl CanvasBackend {
         let canvas = document.get_element_by_id(elem_id)?;
         let canvas: HtmlCanvasElement = canvas.dyn_into().ok()?;
         let context: CanvasRenderingContext2d = canvas.get_context("2d").ok()??.dyn_into().ok()?;
-        Some(CanvasBackend { canvas, context })
+        Some(CanvasBackend {
+            canvas,
+            context,
+            clipped: false,
+        })
     }
 }
 
@@ -59,6 +65,32 @@ fn make_canvas_color<C: Color>(color: &C) -> JsValue {
     format!("rgba({},{},{},{})", r, g, b, a).into()
 }
 
+impl CanvasBackend {
+    fn set_stroke<S: BackendStyle>(&self, style: &S) -> Result<(), DrawingErrorKind<CanvasError>> {
+        self.context
+            .set_stroke_style(&make_canvas_color(style.as_color()));
+        self.context.set_line_width(f64::from(style.stroke_width()));
+        self.context.set_line_cap(match style.line_cap() {
+            LineCap::Butt => "butt",
+            LineCap::Round => "round",
+            LineCap::Square => "square",
+        });
+        self.context.set_line_join(match style.line_join() {
+            LineJoin::Miter => "miter",
+            LineJoin::Round => "round",
+            LineJoin::Bevel => "bevel",
+        });
+        let pattern: Array = style
+            .stroke_dash()
+            .iter()
+            .map(|&l| JsValue::from(l))
+            .collect();
+        self.context
+            .set_line_dash(&pattern)
+            .map_err(|e| DrawingErrorKind::DrawingError(CanvasError(e)))
+    }
+}
+
 impl DrawingBackend for CanvasBackend {
     type ErrorType = CanvasError;
 
@@ -74,6 +106,31 @@ impl DrawingBackend for CanvasBackend {
         Ok(())
     }
 
+    fn set_clip_region(
+        &mut self,
+        region: Option<(BackendCoord, BackendCoord)>,
+    ) -> Result<bool, DrawingErrorKind<CanvasError>> {
+        // The clip regions replace each other, so the state saved for the previous one is
+        // restored first
+        if self.clipped {
+            self.context.restore();
+            self.clipped = false;
+        }
+        if let Some((upper_left, bottom_right)) = region {
+            self.context.save();
+            self.context.begin_path();
+            self.context.rect(
+                f64::from(upper_left.0),
+                f64::from(upper_left.1),
+                f64::from(bottom_right.0 - upper_left.0 + 1),
+                f64::from(bottom_right.1 - upper_left.1 + 1),
+            );
+            self.context.clip();
+            self.clipped = true;
+        }
+        Ok(true)
+    }
+
     fn draw_pixel<S: BackendStyle>(
         &mut self,
         point: BackendCoord,
@@ -92,8 +149,7 @@ impl DrawingBackend for CanvasBackend {
         to: BackendCoord,
         style: &S,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        self.context
-            .set_stroke_style(&make_canvas_color(style.as_color()));
+        self.set_stroke(style)?;
         self.context.begin_path();
         self.context.move_to(f64::from(from.0), f64::from(from.1));
         self.context.line_to(f64::from(to.0), f64::from(to.1));
@@ -118,8 +174,7 @@ impl DrawingBackend for CanvasBackend {
                 f64::from(bottom_right.1 - upper_left.1),
             );
         } else {
-            self.context
-                .set_stroke_style(&make_canvas_color(style.as_color()));
+            self.set_stroke(style)?;
             self.context.stroke_rect(
                 f64::from(upper_left.0),
                 f64::from(upper_left.1),
@@ -138,8 +193,7 @@ impl DrawingBackend for CanvasBackend {
         let mut path = path.into_iter();
         self.context.begin_path();
         if let Some(start) = path.next() {
-            self.context
-                .set_stroke_style(&make_canvas_color(style.as_color()));
+            self.set_stroke(style)?;
             self.context.move_to(f64::from(start.0), f64::from(start.1));
             for next in path {
                 self.context.line_to(f64::from(next.0), f64::from(next.1));
@@ -160,8 +214,7 @@ impl DrawingBackend for CanvasBackend {
             self.context
                 .set_fill_style(&make_canvas_color(style.as_color()));
         } else {
-            self.context
-                .set_stroke_style(&make_canvas_color(style.as_color()));
+            self.set_stroke(style)?;
         }
         self.context.begin_path();
         self.context
@@ -186,15 +239,32 @@ impl DrawingBackend for CanvasBackend {
         text: &str,
         font: &FontDesc<'b>,
         pos: BackendCoord,
+        layout: &TextLayout,
         color: &C,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        self.context.set_text_baseline("bottom");
+        self.context.set_text_align(match layout.h_anchor {
+            HAnchor::Left => "left",
+            HAnchor::Center => "center",
+            HAnchor::Right => "right",
+        });
+        self.context.set_text_baseline(match layout.v_anchor {
+            VAnchor::Top => "top",
+            VAnchor::Middle => "middle",
+            VAnchor::Baseline => "alphabetic",
+            VAnchor::Bottom => "bottom",
+        });
         self.context.set_fill_style(&make_canvas_color(color));
         self.context
             .set_font(&format!("{}px {}", font.get_size(), font.get_name()));
-        self.context
-            .fill_text(text, f64::from(pos.0), f64::from(pos.1) + font.get_size())
-            .map_err(|e| DrawingErrorKind::DrawingError(CanvasError(e)))?;
-        Ok(())
+
+        self.context.save();
+        let result = self
+            .context
+            .translate(f64::from(pos.0), f64::from(pos.1))
+            .and_then(|_| self.context.rotate(-layout.rotation.to_radians()))
+            .and_then(|_| self.context.fill_text(text, 0.0, 0.0));
+        self.context.restore();
+
+        result.map_err(|e| DrawingErrorKind::DrawingError(CanvasError(e)))
     }
 }
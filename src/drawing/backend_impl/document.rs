@@ -0,0 +1,140 @@
+/*!
+The helpers shared by the vector document backends, which write PDF documents and EPS figures.
+Both formats use the same number syntax, string literals and stroke parameters.
+*/
+
+use crate::drawing::backend::BackendStyle;
+use crate::style::{LineCap, LineJoin};
+
+use std::io::{Error, ErrorKind, Write};
+use std::path::Path;
+
+/// Format a number in a document, with at most three decimal places
+pub(super) fn num(value: f64) -> String {
+    let text = format!("{:.3}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// Encode the text as a string literal, in the WinAnsi encoding of PDF or the ISO Latin-1
+/// encoding of PostScript, which agree on the printable Latin-1 characters. The characters that
+/// can't be encoded are replaced by question marks.
+pub(super) fn string_literal(text: &str) -> String {
+    let mut result = String::from("(");
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                result.push('\\');
+                result.push(c);
+            }
+            ' '..='~' => result.push(c),
+            '\u{a0}'..='\u{ff}' => result.push_str(&format!("\\{:03o}", c as u32)),
+            _ => result.push('?'),
+        }
+    }
+    result.push(')');
+    result
+}
+
+/// Find a value in a resource list, or append it to the list
+/// - Returns: The index of the value in the list, which names the resource in the document
+pub(super) fn resource_index(resources: &mut Vec<String>, value: String) -> usize {
+    match resources.iter().position(|r| *r == value) {
+        Some(idx) => idx,
+        None => {
+            resources.push(value);
+            resources.len() - 1
+        }
+    }
+}
+
+/// The stroke parameters of a style, the line cap and the line join are numbered the same way in
+/// both formats
+pub(super) struct StrokeParams {
+    pub width: u32,
+    pub cap: u8,
+    pub join: u8,
+    /// The dash array without the brackets, which is empty for a solid line
+    pub dash: String,
+}
+
+impl StrokeParams {
+    pub(super) fn new<S: BackendStyle>(style: &S) -> Self {
+        let dash: Vec<_> = if style.stroke_dash().iter().any(|&l| l > 0) {
+            style.stroke_dash().iter().map(u32::to_string).collect()
+        } else {
+            vec![]
+        };
+        let cap = match style.line_cap() {
+            LineCap::Butt => 0,
+            LineCap::Round => 1,
+            LineCap::Square => 2,
+        };
+        let join = match style.line_join() {
+            LineJoin::Miter => 0,
+            LineJoin::Round => 1,
+            LineJoin::Bevel => 2,
+        };
+        Self {
+            width: style.stroke_width(),
+            cap,
+            join,
+            dash: dash.join(" "),
+        }
+    }
+}
+
+/// Where the document goes when the backend is presented
+pub(super) enum Target<'a> {
+    /// Save the document to a file
+    File(&'a Path),
+    /// Write the document to a writer, which is released once the document is written
+    Writer(Option<Box<dyn Write + 'a>>),
+}
+
+impl<'a> Target<'a> {
+    /// Create a target which writes the document to a writer
+    pub(super) fn writer<W: Write + 'a>(writer: W) -> Self {
+        Target::Writer(Some(Box::new(writer)))
+    }
+
+    /// Save or write the document. A writer only takes the document once, writing it again is
+    /// an error.
+    /// - `document`: The content of the document
+    /// - `format`: The name of the format, which is used in the error message
+    pub(super) fn write(&mut self, document: &[u8], format: &str) -> Result<(), Error> {
+        match self {
+            Target::File(path) => std::fs::write(path, document),
+            Target::Writer(writer) => match writer.take() {
+                Some(mut writer) => writer.write_all(document).and_then(|_| writer.flush()),
+                None => Err(Error::new(
+                    ErrorKind::Other,
+                    format!("The {} document has already been written", format),
+                )),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_document_helpers() {
+        assert_eq!(num(1.0), "1");
+        assert_eq!(num(-0.0001), "0");
+        assert_eq!(num(0.12345), "0.123");
+        assert_eq!(string_literal("(50°) €"), "(\\(50\\260\\) ?)");
+
+        let mut resources = vec![];
+        assert_eq!(resource_index(&mut resources, "a".to_string()), 0);
+        assert_eq!(resource_index(&mut resources, "b".to_string()), 1);
+        assert_eq!(resource_index(&mut resources, "a".to_string()), 0);
+        assert_eq!(resources.len(), 2);
+    }
+}
@@ -0,0 +1,435 @@
+/*!
+The Encapsulated PostScript drawing backend, which produces a figure that can be embedded in
+other documents, for example LaTeX papers
+*/
+
+use super::document::{num, resource_index, string_literal, StrokeParams, Target};
+use super::std_fonts;
+use super::PresentOnDrop;
+use crate::drawing::backend::{BackendCoord, BackendStyle, DrawingBackend, DrawingErrorKind};
+use crate::style::{Color, FillRule, FontDesc, TextLayout};
+
+use std::fmt::Write as FmtWrite;
+use std::io::{Error, Write};
+use std::path::Path;
+
+/// The Encapsulated PostScript drawing backend. One pixel of the drawing is mapped to one point
+/// of the figure, and the bounding box of the figure is the size of the backend. The fonts are
+/// referenced by name, common font names such as Arial are mapped to the standard PostScript
+/// fonts. The text is laid out with the metrics of the system font, or with approximate metrics
+/// of the standard fonts if it isn't installed. Like the pixels of the bitmap backends, filled
+/// rectangles and clip regions include their bottom right corner.
+///
+/// PostScript doesn't support transparency, so translucent colors are blended with white, which
+/// is what they look like on a white page, and fully transparent drawings are skipped.
+pub struct EPSBackend<'a> {
+    target: Target<'a>,
+    size: (u32, u32),
+    /// The drawing commands of the page
+    content: String,
+    /// The names of the fonts used in the page, the index is the name of the re-encoded font
+    fonts: Vec<String>,
+    /// Flag indicates if a clip region is active, which is a saved graphics state
+    clipped: bool,
+    saved: bool,
+}
+
+impl<'a> EPSBackend<'a> {
+    fn with_target(target: Target<'a>, size: (u32, u32)) -> Self {
+        Self {
+            target,
+            size,
+            content: String::new(),
+            fonts: vec![],
+            clipped: false,
+            saved: false,
+        }
+    }
+
+    /// Create a new EPS drawing backend
+    /// - `path`: The path of the EPS file
+    /// - `size`: The size of the figure in points
+    pub fn new<T: AsRef<Path> + ?Sized>(path: &'a T, size: (u32, u32)) -> Self {
+        Self::with_target(Target::File(path.as_ref()), size)
+    }
+
+    /// Create a new EPS drawing backend which writes the document to a writer when it's
+    /// presented. The document can only be written once, presenting it again is an error.
+    /// - `writer`: The writer the document is written to
+    /// - `size`: The size of the figure in points
+    pub fn with_writer<W: Write + 'a>(writer: W, size: (u32, u32)) -> Self {
+        Self::with_target(Target::writer(writer), size)
+    }
+
+    /// Save the document and consume the backend
+    pub fn finish(mut self) -> Result<(), DrawingErrorKind<Error>> {
+        self.finish_present()
+    }
+
+    /// Set the color of the following drawing commands
+    /// - Returns: `false` if the color is fully transparent and nothing should be drawn
+    fn set_color<C: Color>(&mut self, color: &C) -> bool {
+        let alpha = color.alpha().max(0.0).min(1.0);
+        if alpha == 0.0 {
+            return false;
+        }
+        let (r, g, b) = color.rgb();
+        let blend = |c: u8| num((f64::from(c) * alpha + 255.0 * (1.0 - alpha)) / 255.0);
+        let _ = writeln!(
+            self.content,
+            "{} {} {} setrgbcolor",
+            blend(r),
+            blend(g),
+            blend(b)
+        );
+        true
+    }
+
+    fn set_stroke<S: BackendStyle>(&mut self, style: &S) -> bool {
+        if !self.set_color(style.as_color()) {
+            return false;
+        }
+        let stroke = StrokeParams::new(style);
+        let _ = writeln!(
+            self.content,
+            "{} setlinewidth {} setlinecap {} setlinejoin [{}] 0 setdash",
+            stroke.width, stroke.cap, stroke.join, stroke.dash
+        );
+        true
+    }
+
+    /// Set the color for a fill, or the stroke style for an outline
+    fn set_style<S: BackendStyle>(&mut self, style: &S, fill: bool) -> bool {
+        if fill {
+            self.set_color(style.as_color())
+        } else {
+            self.set_stroke(style)
+        }
+    }
+
+    fn add_polyline<I: IntoIterator<Item = BackendCoord>>(&mut self, points: I) -> bool {
+        let mut empty = true;
+        for (x, y) in points {
+            if empty {
+                self.content.push_str("newpath\n");
+            }
+            let op = if empty { "moveto" } else { "lineto" };
+            let _ = writeln!(self.content, "{} {} {}", x, y, op);
+            empty = false;
+        }
+        !empty
+    }
+
+    /// Build the document with the header comments, the prolog which defines the fonts, and the
+    /// drawing commands
+    fn build_document(&self) -> String {
+        let (w, h) = self.size;
+        let mut document = String::new();
+        let _ = write!(
+            document,
+            "%!PS-Adobe-3.0 EPSF-3.0\n\
+             %%BoundingBox: 0 0 {} {}\n\
+             %%Creator: Plotters\n\
+             %%LanguageLevel: 2\n\
+             %%Pages: 1\n\
+             %%EndComments\n\
+             %%BeginProlog\n",
+            w, h
+        );
+        // Re-encode the fonts, so that the Latin-1 characters can be shown
+        for (idx, font) in self.fonts.iter().enumerate() {
+            let _ = writeln!(
+                document,
+                "/PlottersF{} /{} findfont dup length dict begin \
+                 {{1 index /FID ne {{def}} {{pop pop}} ifelse}} forall \
+                 /Encoding ISOLatin1Encoding def currentdict end definefont pop",
+                idx, font
+            );
+        }
+        let _ = write!(
+            document,
+            "%%EndProlog\n\
+             %%Page: 1 1\n\
+             gsave\n\
+             0 {} translate 1 -1 scale\n\
+             {}{}grestore\n\
+             showpage\n\
+             %%EOF\n",
+            h,
+            self.content,
+            if self.clipped { "grestore\n" } else { "" }
+        );
+        document
+    }
+}
+
+impl<'a> DrawingBackend for EPSBackend<'a> {
+    type ErrorType = Error;
+
+    fn get_size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<Error>> {
+        Ok(())
+    }
+
+    fn present(&mut self) -> Result<(), DrawingErrorKind<Error>> {
+        let document = self.build_document();
+        self.target
+            .write(document.as_bytes(), "EPS")
+            .map_err(DrawingErrorKind::DrawingError)?;
+        self.saved = true;
+        Ok(())
+    }
+
+    fn set_clip_region(
+        &mut self,
+        region: Option<(BackendCoord, BackendCoord)>,
+    ) -> Result<bool, DrawingErrorKind<Error>> {
+        if self.clipped {
+            self.content.push_str("grestore\n");
+            self.clipped = false;
+        }
+        if let Some((upper_left, bottom_right)) = region {
+            let _ = writeln!(
+                self.content,
+                "gsave {} {} {} {} rectclip",
+                upper_left.0,
+                upper_left.1,
+                bottom_right.0 - upper_left.0 + 1,
+                bottom_right.1 - upper_left.1 + 1
+            );
+            self.clipped = true;
+        }
+        Ok(true)
+    }
+
+    fn draw_pixel<C: Color>(
+        &mut self,
+        point: BackendCoord,
+        color: &C,
+    ) -> Result<(), DrawingErrorKind<Error>> {
+        if self.set_color(color) {
+            let _ = writeln!(self.content, "{} {} 1 1 rectfill", point.0, point.1);
+        }
+        Ok(())
+    }
+
+    fn draw_line<S: BackendStyle>(
+        &mut self,
+        from: BackendCoord,
+        to: BackendCoord,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        if self.set_stroke(style) {
+            self.add_polyline(vec![from, to]);
+            self.content.push_str("stroke\n");
+        }
+        Ok(())
+    }
+
+    fn draw_rect<S: BackendStyle>(
+        &mut self,
+        upper_left: BackendCoord,
+        bottom_right: BackendCoord,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        if self.set_style(style, fill) {
+            // A filled rectangle covers the pixels of both corners like the clip region, while an
+            // outline goes through the corners like a line between them
+            let (op, inclusive) = if fill {
+                ("rectfill", 1)
+            } else {
+                ("rectstroke", 0)
+            };
+            let _ = writeln!(
+                self.content,
+                "{} {} {} {} {}",
+                upper_left.0,
+                upper_left.1,
+                bottom_right.0 - upper_left.0 + inclusive,
+                bottom_right.1 - upper_left.1 + inclusive,
+                op
+            );
+        }
+        Ok(())
+    }
+
+    fn draw_path<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        path: I,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        if self.set_stroke(style) && self.add_polyline(path) {
+            self.content.push_str("stroke\n");
+        }
+        Ok(())
+    }
+
+    fn draw_polygon<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        vert: I,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        if self.set_style(style, fill) && self.add_polyline(vert) {
+            let op = match (fill, style.fill_rule()) {
+                (false, _) => "closepath stroke",
+                (true, FillRule::NonZero) => "closepath fill",
+                (true, FillRule::EvenOdd) => "closepath eofill",
+            };
+            self.content.push_str(op);
+            self.content.push('\n');
+        }
+        Ok(())
+    }
+
+    fn draw_circle<S: BackendStyle>(
+        &mut self,
+        center: BackendCoord,
+        radius: u32,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        if self.set_style(style, fill) {
+            let _ = writeln!(
+                self.content,
+                "newpath {} {} {} 0 360 arc closepath {}",
+                center.0,
+                center.1,
+                radius,
+                if fill { "fill" } else { "stroke" }
+            );
+        }
+        Ok(())
+    }
+
+    fn draw_text<'b, C: Color>(
+        &mut self,
+        text: &str,
+        font: &FontDesc<'b>,
+        pos: BackendCoord,
+        layout: &TextLayout,
+        color: &C,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let layout_box = std_fonts::layout_box(font, text);
+        let ((_, min_y), _) = layout_box;
+        let (ax, ay) = layout.anchor_offset_in(layout_box);
+        // The offset of the baseline from the anchor point in the unrotated text
+        let baseline = f64::from(-min_y) - ay;
+
+        let font_idx = resource_index(&mut self.fonts, std_fonts::font_name(font.get_name()));
+        if !self.set_color(color) {
+            return Ok(());
+        }
+        // The page is flipped, so the rotation is negated and the glyphs are flipped back
+        let _ = writeln!(
+            self.content,
+            "gsave {} {} translate {} rotate 1 -1 scale /PlottersF{} findfont {} scalefont \
+             setfont {} {} moveto {} show grestore",
+            pos.0,
+            pos.1,
+            num(-layout.rotation),
+            font_idx,
+            num(font.get_size()),
+            num(-ax),
+            num(-baseline),
+            string_literal(text)
+        );
+        Ok(())
+    }
+}
+
+impl PresentOnDrop for EPSBackend<'_> {
+    fn is_saved(&self) -> bool {
+        self.saved
+    }
+
+    fn set_saved(&mut self) {
+        self.saved = true;
+    }
+}
+
+impl Drop for EPSBackend<'_> {
+    fn drop(&mut self) {
+        self.present_on_drop();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::style::{Mixable, RGBColor};
+
+    #[test]
+    fn test_eps_document() {
+        let mut output = vec![];
+        {
+            let mut backend = EPSBackend::with_writer(&mut output, (200, 100));
+            backend
+                .draw_line((0, 0), (200, 100), &RGBColor(255, 0, 0).mix(0.5))
+                .unwrap();
+            backend
+                .draw_circle((50, 50), 10, &RGBColor(0, 0, 255).mix(0.0), true)
+                .unwrap();
+            backend
+                .draw_text(
+                    "(50°)",
+                    &FontDesc::new("Arial", 12.0),
+                    (10, 10),
+                    &TextLayout::default().rotate(90.0),
+                    &RGBColor(0, 0, 0),
+                )
+                .unwrap();
+            backend.finish().unwrap();
+        }
+
+        let document = String::from_utf8(output).unwrap();
+        assert!(document.starts_with("%!PS-Adobe-3.0 EPSF-3.0\n%%BoundingBox: 0 0 200 100\n"));
+        assert!(document.contains("/PlottersF0 /Helvetica findfont"));
+        assert!(document.contains("0 100 translate 1 -1 scale"));
+        // The translucent red is blended with white, and the transparent circle is skipped
+        assert!(document.contains("1 0.5 0.5 setrgbcolor"));
+        assert!(!document.contains("arc"));
+        assert!(document.contains("-90 rotate"));
+        assert!(document.contains("/PlottersF0 findfont 12 scalefont"));
+        assert!(document.contains("(\\(50\\260\\)) show"));
+        assert!(document.ends_with("showpage\n%%EOF\n"));
+    }
+
+    #[test]
+    fn test_eps_write_once() {
+        let mut output = vec![];
+        {
+            let mut backend = EPSBackend::with_writer(&mut output, (20, 20));
+            backend.present().unwrap();
+            backend.draw_pixel((1, 1), &RGBColor(0, 0, 0)).unwrap();
+            assert!(backend.present().is_err());
+        }
+
+        let document = String::from_utf8(output).unwrap();
+        assert_eq!(document.matches("%!PS-Adobe").count(), 1);
+        assert!(!document.contains("1 1 1 1 rectfill"));
+    }
+
+    #[test]
+    fn test_eps_rect() {
+        let mut output = vec![];
+        {
+            let mut backend = EPSBackend::with_writer(&mut output, (20, 20));
+            let color = RGBColor(0, 0, 0);
+            backend.set_clip_region(Some(((0, 0), (9, 9)))).unwrap();
+            backend.draw_rect((0, 0), (9, 9), &color, true).unwrap();
+            backend.draw_rect((0, 0), (9, 9), &color, false).unwrap();
+            backend.finish().unwrap();
+        }
+
+        // The fill covers the same pixels as the clip region, the outline goes through the corners
+        let document = String::from_utf8(output).unwrap();
+        assert!(document.contains("gsave 0 0 10 10 rectclip"));
+        assert!(document.contains("0 0 10 10 rectfill"));
+        assert!(document.contains("0 0 9 9 rectstroke"));
+    }
+}
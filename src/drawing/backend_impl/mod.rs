@@ -3,12 +3,91 @@ mod svg;
 #[cfg(all(not(target_arch = "wasm32"), feature = "svg"))]
 pub use self::svg::SVGBackend;
 
+#[cfg(all(not(target_arch = "wasm32"), feature = "pdf"))]
+mod pdf;
+#[cfg(all(not(target_arch = "wasm32"), feature = "pdf"))]
+pub use self::pdf::PDFBackend;
+
+#[cfg(all(not(target_arch = "wasm32"), any(feature = "pdf", feature = "eps")))]
+mod document;
+#[cfg(all(not(target_arch = "wasm32"), any(feature = "pdf", feature = "eps")))]
+mod std_fonts;
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "eps"))]
+mod eps;
+#[cfg(all(not(target_arch = "wasm32"), feature = "eps"))]
+pub use self::eps::EPSBackend;
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "terminal"))]
+mod terminal;
+#[cfg(all(not(target_arch = "wasm32"), feature = "terminal"))]
+pub use self::terminal::{CharMode, ColorMode, TerminalBackend};
+
 #[cfg(all(not(target_arch = "wasm32"), feature = "image"))]
 mod bitmap;
 #[cfg(all(not(target_arch = "wasm32"), feature = "image"))]
-pub use bitmap::BitMapBackend;
+pub use bitmap::{BitMapBackend, PixelFormat};
 
 #[cfg(target_arch = "wasm32")]
 mod canvas;
 #[cfg(target_arch = "wasm32")]
 pub use canvas::CanvasBackend;
+
+#[cfg(not(target_arch = "wasm32"))]
+thread_local! {
+    /// The error of the latest present on drop in this thread, which hasn't been taken yet
+    static DROP_ERROR: std::cell::RefCell<Option<Box<dyn std::error::Error>>> =
+        std::cell::RefCell::new(None);
+}
+
+/// Take the error of the latest implicit present of a backend dropped in the current thread.
+/// A backend which is dropped without being presented saves its output, and since `Drop` can't
+/// return the error, it's kept here until it's taken or another dropped backend fails.
+/// - Returns: The error, or `None` if no dropped backend has failed since the last call
+#[cfg(not(target_arch = "wasm32"))]
+pub fn take_drop_error() -> Option<Box<dyn std::error::Error>> {
+    DROP_ERROR.with(|error| error.borrow_mut().take())
+}
+
+/// A backend which presents itself when it's dropped without being presented. The backend's
+/// `finish` consumes it and returns the error of the present, while the error of the implicit
+/// present on drop is recorded for `take_drop_error`.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) trait PresentOnDrop: crate::drawing::DrawingBackend {
+    /// Check if the latest drawing has been presented
+    fn is_saved(&self) -> bool;
+
+    /// Mark the latest drawing as presented
+    fn set_saved(&mut self);
+
+    /// Present the backend unless it has been presented already, which is the body of `finish`
+    fn finish_present(
+        &mut self,
+    ) -> Result<(), crate::drawing::backend::DrawingErrorKind<Self::ErrorType>> {
+        if self.is_saved() {
+            return Ok(());
+        }
+        let result = self.present();
+        // The error is returned to the caller, so dropping the backend shouldn't retry
+        self.set_saved();
+        result
+    }
+
+    /// Present the backend unless it has been presented already, which is the body of `drop`
+    fn present_on_drop(&mut self)
+    where
+        Self::ErrorType: 'static,
+    {
+        if self.is_saved() {
+            return;
+        }
+        if let Err(e) = self.present() {
+            DROP_ERROR.with(|error| *error.borrow_mut() = Some(Box::new(e)));
+        }
+    }
+}
+
+mod recording;
+pub use self::recording::{
+    DisplayList, DrawingCommand, RecordedColor, RecordedStyle, RecordingBackend,
+};
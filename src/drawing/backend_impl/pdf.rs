@@ -0,0 +1,479 @@
+/*!
+The PDF drawing backend, which produces a single-page PDF document with native vector operators
+*/
+
+use super::document::{num, resource_index, string_literal, StrokeParams, Target};
+use super::std_fonts;
+use super::PresentOnDrop;
+use crate::drawing::backend::{BackendCoord, BackendStyle, DrawingBackend, DrawingErrorKind};
+use crate::style::{Color, FillRule, FontDesc, TextLayout};
+
+use std::fmt::Write as FmtWrite;
+use std::io::{Error, Write};
+use std::path::Path;
+
+/// The distance of the Bezier control points from the end points, relative to the radius, which
+/// is used to approximate a quarter of a circle
+const CIRCLE_KAPPA: f64 = 0.552_284_749_8;
+
+/// The PDF drawing backend, which draws a single page. One pixel of the drawing is mapped to one
+/// point of the page. The fonts are referenced by name rather than embedded, common font names
+/// such as Arial are mapped to the standard PDF fonts. The text is laid out with the metrics of
+/// the system font, or with approximate metrics of the standard fonts if it isn't installed.
+/// Like the pixels of the bitmap backends, filled rectangles and clip regions include their
+/// bottom right corner.
+pub struct PDFBackend<'a> {
+    target: Target<'a>,
+    size: (u32, u32),
+    /// The content stream of the page
+    content: String,
+    /// The names of the fonts used in the page, the index is the resource name of the font
+    fonts: Vec<String>,
+    /// The opacity values used in the page, the index is the resource name of the graphics state
+    opacities: Vec<String>,
+    /// Flag indicates if a clip region is active, which is a saved graphics state
+    clipped: bool,
+    saved: bool,
+}
+
+impl<'a> PDFBackend<'a> {
+    fn with_target(target: Target<'a>, size: (u32, u32)) -> Self {
+        Self {
+            target,
+            size,
+            // Flip the page, so that the origin is at the upper left corner like other backends
+            content: format!("1 0 0 -1 0 {} cm\n", size.1),
+            fonts: vec![],
+            opacities: vec![],
+            clipped: false,
+            saved: false,
+        }
+    }
+
+    /// Create a new PDF drawing backend
+    /// - `path`: The path of the PDF file
+    /// - `size`: The size of the page in points
+    pub fn new<T: AsRef<Path> + ?Sized>(path: &'a T, size: (u32, u32)) -> Self {
+        Self::with_target(Target::File(path.as_ref()), size)
+    }
+
+    /// Create a new PDF drawing backend which writes the document to a writer when it's
+    /// presented. The document can only be written once, presenting it again is an error.
+    /// - `writer`: The writer the document is written to
+    /// - `size`: The size of the page in points
+    pub fn with_writer<W: Write + 'a>(writer: W, size: (u32, u32)) -> Self {
+        Self::with_target(Target::writer(writer), size)
+    }
+
+    /// Save the document and consume the backend
+    pub fn finish(mut self) -> Result<(), DrawingErrorKind<Error>> {
+        self.finish_present()
+    }
+
+    /// Set the color of the following strokes or fills, including its opacity
+    fn set_color<C: Color>(&mut self, color: &C, stroke: bool) {
+        let (r, g, b) = color.rgb();
+        let _ = writeln!(
+            self.content,
+            "{} {} {} {}",
+            num(f64::from(r) / 255.0),
+            num(f64::from(g) / 255.0),
+            num(f64::from(b) / 255.0),
+            if stroke { "RG" } else { "rg" }
+        );
+
+        let opacity = num(color.alpha().max(0.0).min(1.0));
+        let idx = resource_index(&mut self.opacities, opacity);
+        let _ = writeln!(self.content, "/GS{} gs", idx);
+    }
+
+    fn set_stroke<S: BackendStyle>(&mut self, style: &S) {
+        self.set_color(style.as_color(), true);
+        let stroke = StrokeParams::new(style);
+        let _ = writeln!(
+            self.content,
+            "{} w {} J {} j [{}] 0 d",
+            stroke.width, stroke.cap, stroke.join, stroke.dash
+        );
+    }
+
+    fn add_polyline<I: IntoIterator<Item = BackendCoord>>(&mut self, points: I) -> bool {
+        let mut empty = true;
+        for (x, y) in points {
+            let op = if empty { "m" } else { "l" };
+            let _ = writeln!(self.content, "{} {} {}", x, y, op);
+            empty = false;
+        }
+        !empty
+    }
+
+    /// Build the document with the objects of the page and the cross-reference table
+    fn build_document(&self) -> Vec<u8> {
+        let mut content = self.content.clone();
+        if self.clipped {
+            content.push_str("Q\n");
+        }
+
+        let first_font = 5;
+        let first_state = first_font + self.fonts.len();
+
+        let font_resources: String = (0..self.fonts.len())
+            .map(|idx| format!("/F{} {} 0 R ", idx, first_font + idx))
+            .collect();
+        let state_resources: String = (0..self.opacities.len())
+            .map(|idx| format!("/GS{} {} 0 R ", idx, first_state + idx))
+            .collect();
+
+        let mut objects = vec![
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << {}>> \
+                 /ExtGState << {}>> >> /Contents 4 0 R >>",
+                self.size.0, self.size.1, font_resources, state_resources
+            ),
+            format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                content.len(),
+                content
+            ),
+        ];
+        for font in self.fonts.iter() {
+            objects.push(format!(
+                "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+                font
+            ));
+        }
+        for opacity in self.opacities.iter() {
+            objects.push(format!(
+                "<< /Type /ExtGState /CA {} /ca {} >>",
+                opacity, opacity
+            ));
+        }
+
+        let mut document = String::from("%PDF-1.4\n");
+        let mut offsets = vec![];
+        for (idx, object) in objects.iter().enumerate() {
+            offsets.push(document.len());
+            let _ = write!(document, "{} 0 obj\n{}\nendobj\n", idx + 1, object);
+        }
+
+        let xref = document.len();
+        let _ = write!(
+            document,
+            "xref\n0 {}\n0000000000 65535 f \n",
+            objects.len() + 1
+        );
+        for offset in offsets {
+            let _ = writeln!(document, "{:010} 00000 n ", offset);
+        }
+        let _ = write!(
+            document,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        );
+
+        document.into_bytes()
+    }
+}
+
+impl<'a> DrawingBackend for PDFBackend<'a> {
+    type ErrorType = Error;
+
+    fn get_size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<Error>> {
+        Ok(())
+    }
+
+    fn present(&mut self) -> Result<(), DrawingErrorKind<Error>> {
+        let document = self.build_document();
+        self.target
+            .write(&document, "PDF")
+            .map_err(DrawingErrorKind::DrawingError)?;
+        self.saved = true;
+        Ok(())
+    }
+
+    fn set_clip_region(
+        &mut self,
+        region: Option<(BackendCoord, BackendCoord)>,
+    ) -> Result<bool, DrawingErrorKind<Error>> {
+        if self.clipped {
+            self.content.push_str("Q\n");
+            self.clipped = false;
+        }
+        if let Some((upper_left, bottom_right)) = region {
+            let _ = writeln!(
+                self.content,
+                "q {} {} {} {} re W n",
+                upper_left.0,
+                upper_left.1,
+                bottom_right.0 - upper_left.0 + 1,
+                bottom_right.1 - upper_left.1 + 1
+            );
+            self.clipped = true;
+        }
+        Ok(true)
+    }
+
+    fn draw_pixel<C: Color>(
+        &mut self,
+        point: BackendCoord,
+        color: &C,
+    ) -> Result<(), DrawingErrorKind<Error>> {
+        self.set_color(color, false);
+        let _ = writeln!(self.content, "{} {} 1 1 re f", point.0, point.1);
+        Ok(())
+    }
+
+    fn draw_line<S: BackendStyle>(
+        &mut self,
+        from: BackendCoord,
+        to: BackendCoord,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.set_stroke(style);
+        self.add_polyline(vec![from, to]);
+        self.content.push_str("S\n");
+        Ok(())
+    }
+
+    fn draw_rect<S: BackendStyle>(
+        &mut self,
+        upper_left: BackendCoord,
+        bottom_right: BackendCoord,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        // A filled rectangle covers the pixels of both corners like the clip region, while an
+        // outline goes through the corners like a line between them
+        let (op, inclusive) = if fill {
+            self.set_color(style.as_color(), false);
+            ("f", 1)
+        } else {
+            self.set_stroke(style);
+            ("S", 0)
+        };
+        let _ = writeln!(
+            self.content,
+            "{} {} {} {} re {}",
+            upper_left.0,
+            upper_left.1,
+            bottom_right.0 - upper_left.0 + inclusive,
+            bottom_right.1 - upper_left.1 + inclusive,
+            op
+        );
+        Ok(())
+    }
+
+    fn draw_path<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        path: I,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.set_stroke(style);
+        if self.add_polyline(path) {
+            self.content.push_str("S\n");
+        }
+        Ok(())
+    }
+
+    fn draw_polygon<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        vert: I,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        if fill {
+            self.set_color(style.as_color(), false);
+        } else {
+            self.set_stroke(style);
+        }
+        if self.add_polyline(vert) {
+            let op = match (fill, style.fill_rule()) {
+                (false, _) => "h S",
+                (true, FillRule::NonZero) => "h f",
+                (true, FillRule::EvenOdd) => "h f*",
+            };
+            self.content.push_str(op);
+            self.content.push('\n');
+        }
+        Ok(())
+    }
+
+    fn draw_circle<S: BackendStyle>(
+        &mut self,
+        center: BackendCoord,
+        radius: u32,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        if fill {
+            self.set_color(style.as_color(), false);
+        } else {
+            self.set_stroke(style);
+        }
+
+        let (x, y, r) = (f64::from(center.0), f64::from(center.1), f64::from(radius));
+        let k = r * CIRCLE_KAPPA;
+        let _ = writeln!(self.content, "{} {} m", num(x + r), num(y));
+        for &(c1, c2, end) in [
+            ((x + r, y + k), (x + k, y + r), (x, y + r)),
+            ((x - k, y + r), (x - r, y + k), (x - r, y)),
+            ((x - r, y - k), (x - k, y - r), (x, y - r)),
+            ((x + k, y - r), (x + r, y - k), (x + r, y)),
+        ]
+        .iter()
+        {
+            let _ = writeln!(
+                self.content,
+                "{} {} {} {} {} {} c",
+                num(c1.0),
+                num(c1.1),
+                num(c2.0),
+                num(c2.1),
+                num(end.0),
+                num(end.1)
+            );
+        }
+        self.content.push_str(if fill { "h f\n" } else { "h S\n" });
+        Ok(())
+    }
+
+    fn draw_text<'b, C: Color>(
+        &mut self,
+        text: &str,
+        font: &FontDesc<'b>,
+        pos: BackendCoord,
+        layout: &TextLayout,
+        color: &C,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let layout_box = std_fonts::layout_box(font, text);
+        let ((_, min_y), _) = layout_box;
+        let (ax, ay) = layout.anchor_offset_in(layout_box);
+        // The offset of the baseline from the anchor point in the unrotated text
+        let baseline = f64::from(-min_y) - ay;
+        let (sin, cos) = layout.rotation.to_radians().sin_cos();
+        let (x, y) = (f64::from(pos.0), f64::from(pos.1));
+
+        let font_idx = resource_index(&mut self.fonts, std_fonts::font_name(font.get_name()));
+        self.set_color(color, false);
+        // The text matrix flips the glyphs back, since the page is flipped
+        let _ = writeln!(
+            self.content,
+            "BT /F{} {} Tf {} {} {} {} {} {} Tm {} Tj ET",
+            font_idx,
+            num(font.get_size()),
+            num(cos),
+            num(-sin),
+            num(-sin),
+            num(-cos),
+            num(x - ax * cos + baseline * sin),
+            num(y + ax * sin + baseline * cos),
+            string_literal(text)
+        );
+        Ok(())
+    }
+}
+
+impl PresentOnDrop for PDFBackend<'_> {
+    fn is_saved(&self) -> bool {
+        self.saved
+    }
+
+    fn set_saved(&mut self) {
+        self.saved = true;
+    }
+}
+
+impl Drop for PDFBackend<'_> {
+    fn drop(&mut self) {
+        self.present_on_drop();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::style::{Mixable, RGBColor};
+
+    #[test]
+    fn test_pdf_document() {
+        let mut output = vec![];
+        {
+            let mut backend = PDFBackend::with_writer(&mut output, (200, 100));
+            backend
+                .draw_line((0, 0), (200, 100), &RGBColor(255, 0, 0))
+                .unwrap();
+            backend
+                .draw_text(
+                    "(50°)",
+                    &FontDesc::new("Arial", 12.0),
+                    (10, 10),
+                    &TextLayout::default(),
+                    &RGBColor(0, 0, 0).mix(0.5),
+                )
+                .unwrap();
+            backend.finish().unwrap();
+        }
+
+        let document = String::from_utf8_lossy(&output).to_string();
+        assert!(document.starts_with("%PDF-1.4\n"));
+        assert!(document.contains("/MediaBox [0 0 200 100]"));
+        assert!(document.contains("/BaseFont /Helvetica"));
+        assert!(document.contains("/CA 0.5"));
+        assert!(document.contains("(\\(50\\260\\)) Tj"));
+
+        // Every entry of the cross-reference table points to its object
+        let xref = document.find("\nxref\n").unwrap() + 1;
+        let entries: Vec<_> = document[xref..].lines().skip(3).collect();
+        for (idx, entry) in entries
+            .iter()
+            .take_while(|l| l.ends_with(" n "))
+            .enumerate()
+        {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(document[offset..].starts_with(&format!("{} 0 obj", idx + 1)));
+        }
+        let startxref: usize = document.lines().rev().nth(1).unwrap().parse().unwrap();
+        assert_eq!(startxref, xref);
+    }
+
+    #[test]
+    fn test_pdf_write_once() {
+        let mut output = vec![];
+        {
+            let mut backend = PDFBackend::with_writer(&mut output, (20, 20));
+            backend.present().unwrap();
+            backend.draw_pixel((1, 1), &RGBColor(0, 0, 0)).unwrap();
+            assert!(backend.present().is_err());
+        }
+
+        let document = String::from_utf8_lossy(&output).to_string();
+        assert_eq!(document.matches("%PDF-1.4").count(), 1);
+        assert!(!document.contains("1 1 1 1 re f"));
+    }
+
+    #[test]
+    fn test_pdf_rect() {
+        let mut output = vec![];
+        {
+            let mut backend = PDFBackend::with_writer(&mut output, (20, 20));
+            let color = RGBColor(0, 0, 0);
+            backend.set_clip_region(Some(((0, 0), (9, 9)))).unwrap();
+            backend.draw_rect((0, 0), (9, 9), &color, true).unwrap();
+            backend.draw_rect((0, 0), (9, 9), &color, false).unwrap();
+            backend.finish().unwrap();
+        }
+
+        // The fill covers the same pixels as the clip region, the outline goes through the corners
+        let document = String::from_utf8_lossy(&output).to_string();
+        assert!(document.contains("q 0 0 10 10 re W n"));
+        assert!(document.contains("0 0 10 10 re f"));
+        assert!(document.contains("0 0 9 9 re S"));
+    }
+}
@@ -0,0 +1,623 @@
+/*!
+The recording drawing backend, which keeps the drawing as a display list that can be replayed
+on any other backend
+*/
+
+use crate::drawing::backend::{BackendCoord, BackendStyle, DrawingBackend, DrawingErrorKind};
+use crate::style::{Color, FillRule, FontDesc, LineCap, LineJoin, TextLayout};
+
+use std::convert::Infallible;
+
+/// A color in a display list
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordedColor {
+    /// The red, green and blue components
+    pub rgb: (u8, u8, u8),
+    /// The alpha channel
+    pub alpha: f64,
+}
+
+impl RecordedColor {
+    /// Record a color, which is also useful to compare a recorded color with a known one
+    pub fn from_color<C: Color + ?Sized>(color: &C) -> Self {
+        Self {
+            rgb: color.rgb(),
+            alpha: color.alpha(),
+        }
+    }
+}
+
+impl Color for RecordedColor {
+    fn rgb(&self) -> (u8, u8, u8) {
+        self.rgb
+    }
+
+    fn alpha(&self) -> f64 {
+        self.alpha
+    }
+}
+
+/// A shape style in a display list
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordedStyle {
+    /// The color of the shape
+    pub color: RecordedColor,
+    /// The stroke width in pixels
+    pub stroke_width: u32,
+    /// The dash pattern of the stroke
+    pub stroke_dash: Vec<u32>,
+    /// How the end points of an open stroke are drawn
+    pub line_cap: LineCap,
+    /// How the connected segments of a stroke are joined
+    pub line_join: LineJoin,
+    /// Which part of a self-intersecting polygon is filled
+    pub fill_rule: FillRule,
+}
+
+impl RecordedStyle {
+    fn from_style<S: BackendStyle>(style: &S) -> Self {
+        Self {
+            color: RecordedColor::from_color(style.as_color()),
+            stroke_width: style.stroke_width(),
+            stroke_dash: style.stroke_dash().to_vec(),
+            line_cap: style.line_cap(),
+            line_join: style.line_join(),
+            fill_rule: style.fill_rule(),
+        }
+    }
+
+    fn scale(&self, factor: f64) -> Self {
+        let scale = |value: u32| (f64::from(value) * factor).round().max(1.0) as u32;
+        Self {
+            stroke_width: scale(self.stroke_width),
+            stroke_dash: self.stroke_dash.iter().map(|&l| scale(l)).collect(),
+            ..self.clone()
+        }
+    }
+}
+
+impl BackendStyle for RecordedStyle {
+    type ColorType = RecordedColor;
+
+    fn as_color(&self) -> &RecordedColor {
+        &self.color
+    }
+
+    fn stroke_width(&self) -> u32 {
+        self.stroke_width
+    }
+
+    fn stroke_dash(&self) -> &[u32] {
+        &self.stroke_dash
+    }
+
+    fn line_cap(&self) -> LineCap {
+        self.line_cap
+    }
+
+    fn line_join(&self) -> LineJoin {
+        self.line_join
+    }
+
+    fn fill_rule(&self) -> FillRule {
+        self.fill_rule
+    }
+}
+
+/// A primitive drawing call of a display list
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DrawingCommand {
+    /// A call of `DrawingBackend::draw_pixel`
+    Pixel {
+        point: BackendCoord,
+        color: RecordedColor,
+    },
+    /// A call of `DrawingBackend::draw_line`
+    Line {
+        from: BackendCoord,
+        to: BackendCoord,
+        style: RecordedStyle,
+    },
+    /// A call of `DrawingBackend::draw_rect`
+    Rect {
+        upper_left: BackendCoord,
+        bottom_right: BackendCoord,
+        style: RecordedStyle,
+        fill: bool,
+    },
+    /// A call of `DrawingBackend::draw_path`
+    Path {
+        points: Vec<BackendCoord>,
+        style: RecordedStyle,
+    },
+    /// A call of `DrawingBackend::draw_polygon`
+    Polygon {
+        points: Vec<BackendCoord>,
+        style: RecordedStyle,
+        fill: bool,
+    },
+    /// A call of `DrawingBackend::draw_circle`
+    Circle {
+        center: BackendCoord,
+        radius: u32,
+        style: RecordedStyle,
+        fill: bool,
+    },
+    /// A call of `DrawingBackend::draw_text`, the font is described by its name and size
+    Text {
+        text: String,
+        font: String,
+        font_size: f64,
+        pos: BackendCoord,
+        layout: TextLayout,
+        color: RecordedColor,
+    },
+}
+
+impl DrawingCommand {
+    /// Get the color of the drawing call
+    pub fn color(&self) -> &RecordedColor {
+        match self {
+            DrawingCommand::Pixel { color, .. } | DrawingCommand::Text { color, .. } => color,
+            DrawingCommand::Line { style, .. }
+            | DrawingCommand::Rect { style, .. }
+            | DrawingCommand::Path { style, .. }
+            | DrawingCommand::Polygon { style, .. }
+            | DrawingCommand::Circle { style, .. } => &style.color,
+        }
+    }
+}
+
+/// A list of drawing calls recorded by the `RecordingBackend`
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplayList {
+    /// The size of the drawing the calls were recorded on
+    pub size: (u32, u32),
+    /// The drawing calls in order
+    pub commands: Vec<DrawingCommand>,
+}
+
+impl DisplayList {
+    /// Draw the display list on a backend, in the original size
+    /// - `backend`: The backend to draw on
+    pub fn replay<DB: DrawingBackend>(
+        &self,
+        backend: &mut DB,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        self.replay_with_scale(backend, (1.0, 1.0))
+    }
+
+    /// Draw the display list on a backend, scaled to the size of the backend. The coordinates
+    /// are scaled in each direction separately, while the stroke widths, radiuses and font sizes
+    /// are scaled by the smaller factor.
+    /// - `backend`: The backend to draw on
+    pub fn replay_scaled<DB: DrawingBackend>(
+        &self,
+        backend: &mut DB,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        let (w, h) = backend.get_size();
+        let scale = (
+            f64::from(w) / f64::from(self.size.0.max(1)),
+            f64::from(h) / f64::from(self.size.1.max(1)),
+        );
+        self.replay_with_scale(backend, scale)
+    }
+
+    fn replay_with_scale<DB: DrawingBackend>(
+        &self,
+        backend: &mut DB,
+        (sx, sy): (f64, f64),
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        let point = |(x, y): BackendCoord| {
+            (
+                (f64::from(x) * sx).round() as i32,
+                (f64::from(y) * sy).round() as i32,
+            )
+        };
+        // The pixels of a rectangle are scaled to all the pixels they cover
+        let corners = |(x0, y0): BackendCoord, (x1, y1): BackendCoord| {
+            (
+                ((f64::from(x0) * sx) as i32, (f64::from(y0) * sy) as i32),
+                (
+                    ((f64::from(x1 + 1) * sx) as i32 - 1).max((f64::from(x0) * sx) as i32),
+                    ((f64::from(y1 + 1) * sy) as i32 - 1).max((f64::from(y0) * sy) as i32),
+                ),
+            )
+        };
+        let points = |points: &[BackendCoord]| points.iter().map(|&p| point(p)).collect::<Vec<_>>();
+        let factor = sx.min(sy);
+        let length = |value: u32| (f64::from(value) * factor).round() as u32;
+
+        backend.ensure_prepared()?;
+        for command in self.commands.iter() {
+            match command {
+                DrawingCommand::Pixel { point, color } => {
+                    let (upper_left, bottom_right) = corners(*point, *point);
+                    if upper_left == bottom_right {
+                        backend.draw_pixel(upper_left, color)?;
+                    } else {
+                        backend.draw_rect(upper_left, bottom_right, color, true)?;
+                    }
+                }
+                DrawingCommand::Line { from, to, style } => {
+                    backend.draw_line(point(*from), point(*to), &style.scale(factor))?;
+                }
+                DrawingCommand::Rect {
+                    upper_left,
+                    bottom_right,
+                    style,
+                    fill,
+                } => {
+                    let (upper_left, bottom_right) = corners(
+                        (
+                            upper_left.0.min(bottom_right.0),
+                            upper_left.1.min(bottom_right.1),
+                        ),
+                        (
+                            upper_left.0.max(bottom_right.0),
+                            upper_left.1.max(bottom_right.1),
+                        ),
+                    );
+                    backend.draw_rect(upper_left, bottom_right, &style.scale(factor), *fill)?;
+                }
+                DrawingCommand::Path {
+                    points: path,
+                    style,
+                } => {
+                    backend.draw_path(points(path), &style.scale(factor))?;
+                }
+                DrawingCommand::Polygon {
+                    points: vert,
+                    style,
+                    fill,
+                } => {
+                    backend.draw_polygon(points(vert), &style.scale(factor), *fill)?;
+                }
+                DrawingCommand::Circle {
+                    center,
+                    radius,
+                    style,
+                    fill,
+                } => {
+                    backend.draw_circle(
+                        point(*center),
+                        length(*radius),
+                        &style.scale(factor),
+                        *fill,
+                    )?;
+                }
+                DrawingCommand::Text {
+                    text,
+                    font,
+                    font_size,
+                    pos,
+                    layout,
+                    color,
+                } => {
+                    let font = FontDesc::new(font, font_size * factor);
+                    backend.draw_text(text, &font, point(*pos), layout, color)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The queries of the recorded drawing calls, which are meant for testing the code that draws
+/// charts without comparing images.
+impl DisplayList {
+    /// Check if a straight line between the two points has been drawn, in either direction.
+    /// Both the lines and the segments of paths are considered.
+    pub fn has_line(&self, from: BackendCoord, to: BackendCoord) -> bool {
+        let matches =
+            |a: &BackendCoord, b: &BackendCoord| (*a, *b) == (from, to) || (*a, *b) == (to, from);
+        self.commands.iter().any(|command| match command {
+            DrawingCommand::Line { from, to, .. } => matches(from, to),
+            DrawingCommand::Path { points, .. } => points.windows(2).any(|s| matches(&s[0], &s[1])),
+            _ => false,
+        })
+    }
+
+    /// Count the circles whose centers are in the rectangle, including its edges
+    /// - `upper_left`: The upper left corner of the rectangle
+    /// - `bottom_right`: The bottom right corner of the rectangle
+    pub fn count_circles_in(&self, upper_left: BackendCoord, bottom_right: BackendCoord) -> usize {
+        self.commands
+            .iter()
+            .filter(|command| match command {
+                DrawingCommand::Circle { center: (x, y), .. } => {
+                    (upper_left.0..=bottom_right.0).contains(x)
+                        && (upper_left.1..=bottom_right.1).contains(y)
+                }
+                _ => false,
+            })
+            .count()
+    }
+
+    /// Get the text drawn in order, with the position of each text
+    pub fn texts(&self) -> Vec<(&str, BackendCoord)> {
+        self.commands
+            .iter()
+            .filter_map(|command| match command {
+                DrawingCommand::Text { text, pos, .. } => Some((text.as_str(), *pos)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Get the drawing calls that use the color, ignoring the alpha channel
+    pub fn with_color<C: Color>(&self, color: &C) -> impl Iterator<Item = &DrawingCommand> {
+        let rgb = color.rgb();
+        self.commands
+            .iter()
+            .filter(move |command| command.color().rgb == rgb)
+    }
+}
+
+/// The drawing backend which records the drawing calls into a `DisplayList` owned by the caller,
+/// so that one drawing can be rendered by several backends, for example as both a bitmap and a
+/// SVG image, without computing the chart again.
+///
+/// It's also the backend for testing the code that draws charts, since the display list can be
+/// queried for the lines, circles and text that were drawn. Text is recorded without rendering
+/// it, so it doesn't need the font to be installed. However, the layout of the labels and
+/// captions still measures the text with the font.
+///
+/// The recorded calls are already clipped to the drawing areas, so the display list doesn't
+/// depend on the clipping support of the backend it's replayed on.
+pub struct RecordingBackend<'a> {
+    list: &'a mut DisplayList,
+}
+
+impl<'a> RecordingBackend<'a> {
+    /// Create a new recording backend
+    /// - `list`: The display list the drawing calls are recorded into, its previous content is
+    ///   replaced
+    /// - `size`: The size of the drawing
+    pub fn new(list: &'a mut DisplayList, size: (u32, u32)) -> Self {
+        list.size = size;
+        list.commands.clear();
+        Self { list }
+    }
+
+    fn record(&mut self, command: DrawingCommand) -> Result<(), DrawingErrorKind<Infallible>> {
+        self.list.commands.push(command);
+        Ok(())
+    }
+}
+
+impl<'a> DrawingBackend for RecordingBackend<'a> {
+    type ErrorType = Infallible;
+
+    fn get_size(&self) -> (u32, u32) {
+        self.list.size
+    }
+
+    fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<Infallible>> {
+        Ok(())
+    }
+
+    fn present(&mut self) -> Result<(), DrawingErrorKind<Infallible>> {
+        Ok(())
+    }
+
+    fn draw_pixel<C: Color>(
+        &mut self,
+        point: BackendCoord,
+        color: &C,
+    ) -> Result<(), DrawingErrorKind<Infallible>> {
+        self.record(DrawingCommand::Pixel {
+            point,
+            color: RecordedColor::from_color(color),
+        })
+    }
+
+    fn draw_line<S: BackendStyle>(
+        &mut self,
+        from: BackendCoord,
+        to: BackendCoord,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Infallible>> {
+        self.record(DrawingCommand::Line {
+            from,
+            to,
+            style: RecordedStyle::from_style(style),
+        })
+    }
+
+    fn draw_rect<S: BackendStyle>(
+        &mut self,
+        upper_left: BackendCoord,
+        bottom_right: BackendCoord,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Infallible>> {
+        self.record(DrawingCommand::Rect {
+            upper_left,
+            bottom_right,
+            style: RecordedStyle::from_style(style),
+            fill,
+        })
+    }
+
+    fn draw_path<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        path: I,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Infallible>> {
+        self.record(DrawingCommand::Path {
+            points: path.into_iter().collect(),
+            style: RecordedStyle::from_style(style),
+        })
+    }
+
+    fn draw_polygon<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        vert: I,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Infallible>> {
+        self.record(DrawingCommand::Polygon {
+            points: vert.into_iter().collect(),
+            style: RecordedStyle::from_style(style),
+            fill,
+        })
+    }
+
+    fn draw_circle<S: BackendStyle>(
+        &mut self,
+        center: BackendCoord,
+        radius: u32,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Infallible>> {
+        self.record(DrawingCommand::Circle {
+            center,
+            radius,
+            style: RecordedStyle::from_style(style),
+            fill,
+        })
+    }
+
+    fn draw_text<'b, C: Color>(
+        &mut self,
+        text: &str,
+        font: &FontDesc<'b>,
+        pos: BackendCoord,
+        layout: &TextLayout,
+        color: &C,
+    ) -> Result<(), DrawingErrorKind<Infallible>> {
+        self.record(DrawingCommand::Text {
+            text: text.to_string(),
+            font: font.get_name().to_string(),
+            font_size: font.get_size(),
+            pos,
+            layout: *layout,
+            color: RecordedColor::from_color(color),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::drawing::DrawingArea;
+    use crate::element::Circle;
+    use crate::style::{Mixable, RGBColor, ShapeStyle};
+
+    #[test]
+    fn test_record_and_replay() {
+        let mut list = DisplayList::default();
+        {
+            let root: DrawingArea<_, _> = RecordingBackend::new(&mut list, (100, 50)).into();
+            root.fill(&RGBColor(255, 255, 255)).unwrap();
+            let color = RGBColor(255, 0, 0).mix(0.5);
+            root.draw(&Circle::new((10, 10), 5, ShapeStyle::from(&color)))
+                .unwrap();
+            root.draw_pixel((3, 4), &RGBColor(0, 0, 255)).unwrap();
+        }
+
+        assert_eq!(list.size, (100, 50));
+        assert_eq!(list.commands.len(), 3);
+        match &list.commands[1] {
+            DrawingCommand::Circle {
+                center,
+                radius,
+                style,
+                fill,
+            } => {
+                assert_eq!((*center, *radius, *fill), ((10, 10), 5, false));
+                assert_eq!(style.color.rgb, (255, 0, 0));
+                assert_eq!(style.color.alpha, 0.5);
+            }
+            command => panic!("Unexpected command {:?}", command),
+        }
+
+        let mut replayed = DisplayList::default();
+        list.replay(&mut RecordingBackend::new(&mut replayed, (100, 50)))
+            .unwrap();
+        assert_eq!(replayed, list);
+
+        let mut scaled = DisplayList::default();
+        list.replay_scaled(&mut RecordingBackend::new(&mut scaled, (200, 100)))
+            .unwrap();
+        assert_eq!(
+            scaled.commands[0],
+            DrawingCommand::Rect {
+                upper_left: (0, 0),
+                bottom_right: (201, 101),
+                style: RecordedStyle {
+                    stroke_width: 2,
+                    ..RecordedStyle::from_style(&RGBColor(255, 255, 255))
+                },
+                fill: true,
+            }
+        );
+        match &scaled.commands[1] {
+            DrawingCommand::Circle { center, radius, .. } => {
+                assert_eq!((*center, *radius), ((20, 20), 10))
+            }
+            command => panic!("Unexpected command {:?}", command),
+        }
+        assert_eq!(
+            scaled.commands[2],
+            DrawingCommand::Rect {
+                upper_left: (6, 8),
+                bottom_right: (7, 9),
+                style: RecordedStyle::from_style(&RGBColor(0, 0, 255)),
+                fill: true,
+            }
+        );
+    }
+    #[test]
+    fn test_chart_queries() {
+        use crate::chart::ChartBuilder;
+        use crate::series::LineSeries;
+        use crate::style::FontDesc;
+
+        let font = FontDesc::new("no-such-font", 12.0);
+        let mut list = DisplayList::default();
+        let (first, second, lower, upper) = {
+            let root: DrawingArea<_, _> = RecordingBackend::new(&mut list, (400, 300)).into();
+            let mut chart = ChartBuilder::on(&root)
+                .margin(10)
+                .x_label_area_size(30)
+                .y_label_area_size(30)
+                .build_ranged(-1..10, -1..10)
+                .unwrap();
+            chart.configure_mesh().label_style(&font).draw().unwrap();
+            chart
+                .draw_series(LineSeries::new(
+                    (0..10).map(|x| (x, x)),
+                    &RGBColor(255, 0, 0),
+                ))
+                .unwrap();
+            chart
+                .draw_series(
+                    (0..10).map(|x| Circle::new((x, x), 3, ShapeStyle::from(&RGBColor(0, 0, 255)))),
+                )
+                .unwrap();
+            (
+                chart.backend_coord(&(0, 0)),
+                chart.backend_coord(&(1, 1)),
+                chart.backend_coord(&(2, 2)),
+                chart.backend_coord(&(4, 4)),
+            )
+        };
+
+        assert!(list.has_line(first, second));
+        assert!(list.has_line(second, first));
+        assert!(!list.has_line(first, (0, 0)));
+        assert_eq!(list.count_circles_in((0, 0), (400, 300)), 10);
+        assert_eq!(
+            list.count_circles_in((lower.0, upper.1), (upper.0, lower.1)),
+            3
+        );
+        assert_eq!(list.with_color(&RGBColor(255, 0, 0)).count(), 1);
+        assert!(list.texts().iter().any(|(text, _)| *text == "4"));
+    }
+}
@@ -0,0 +1,144 @@
+/*!
+The standard fonts, which every PDF and PostScript reader provides. The vector document backends
+map the common font names to them, and use their metrics to lay out the text when the font can't
+be loaded from the system.
+*/
+
+use crate::style::FontDesc;
+
+/// The widths of the printable ASCII characters in Helvetica, in thousandths of the font size
+const HELVETICA_WIDTHS: [u32; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278,
+    278, // ' '..='/'
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584,
+    556, // '0'..='?'
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722,
+    778, // '@'..='O'
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469,
+    556, // 'P'..='_'
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556,
+    556, // '`'..='o'
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, // 'p'..='~'
+];
+
+/// The width of the characters which are not in the width table
+const DEFAULT_WIDTH: u32 = 556;
+
+/// A standard font
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum StdFont {
+    Helvetica,
+    TimesRoman,
+    Courier,
+}
+
+impl StdFont {
+    /// Map the common font names to the standard fonts
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "arial" | "helvetica" | "sans" | "sans-serif" => Some(StdFont::Helvetica),
+            "times" | "times new roman" | "serif" => Some(StdFont::TimesRoman),
+            "courier" | "courier new" | "monospace" => Some(StdFont::Courier),
+            _ => None,
+        }
+    }
+
+    /// The name of the font in the document
+    fn name(self) -> &'static str {
+        match self {
+            StdFont::Helvetica => "Helvetica",
+            StdFont::TimesRoman => "Times-Roman",
+            StdFont::Courier => "Courier",
+        }
+    }
+
+    /// The ascent and the descent of the font, in thousandths of the font size
+    fn vertical_metrics(self) -> (u32, u32) {
+        match self {
+            StdFont::Helvetica => (718, 207),
+            StdFont::TimesRoman => (683, 217),
+            StdFont::Courier => (629, 157),
+        }
+    }
+
+    /// The width of a character, in thousandths of the font size. Courier is monospaced, and
+    /// the other fonts are approximated with the widths of Helvetica.
+    fn char_width(self, c: char) -> u32 {
+        match (self, c) {
+            (StdFont::Courier, _) => 600,
+            (_, ' '..='~') => HELVETICA_WIDTHS[c as usize - ' ' as usize],
+            _ => DEFAULT_WIDTH,
+        }
+    }
+}
+
+/// Get the name of the font in the document. The common font names are mapped to the standard
+/// fonts, while other fonts are referenced by their name. Helvetica is used when nothing is left
+/// of the name after removing the characters a PDF or PostScript name can't contain.
+pub(super) fn font_name(name: &str) -> String {
+    let font = StdFont::from_name(name).map(StdFont::name);
+    let name: String = name
+        .chars()
+        .filter(|c| c.is_ascii_graphic() && !"()<>[]{}/%#".contains(*c))
+        .collect();
+    match font {
+        Some(font) => font.to_string(),
+        None if name.is_empty() => StdFont::Helvetica.name().to_string(),
+        None => name,
+    }
+}
+
+/// Get the box of the text relative to the origin of its baseline, like `FontDesc::layout_box`.
+/// When the font can't be loaded, the box is approximated with the metrics of the standard font
+/// the font name is mapped to, or with the metrics of Helvetica.
+pub(super) fn layout_box(font: &FontDesc, text: &str) -> ((i32, i32), (i32, i32)) {
+    font.layout_box(text)
+        .unwrap_or_else(|_| approximate_layout_box(font.get_name(), font.get_size(), text))
+}
+
+fn approximate_layout_box(name: &str, size: f64, text: &str) -> ((i32, i32), (i32, i32)) {
+    let font = StdFont::from_name(name).unwrap_or(StdFont::Helvetica);
+    let (ascent, descent) = font.vertical_metrics();
+    let width: u32 = text.chars().map(|c| font.char_width(c)).sum();
+    let scale = |value: u32| (f64::from(value) * size / 1000.0).round() as i32;
+    ((0, -scale(ascent)), (scale(width), scale(descent)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_font_name() {
+        assert_eq!(font_name("Arial"), "Helvetica");
+        assert_eq!(font_name("courier new"), "Courier");
+        assert_eq!(font_name("DejaVu Sans (Mono)"), "DejaVuSansMono");
+        assert_eq!(font_name("宋体"), "Helvetica");
+    }
+
+    #[test]
+    fn test_approximate_layout_box() {
+        assert_eq!(StdFont::from_name("Arial"), Some(StdFont::Helvetica));
+        assert_eq!(StdFont::from_name("no-such-font"), None);
+
+        // H is 722 and i is 222 thousandths wide
+        assert_eq!(
+            approximate_layout_box("Helvetica", 10.0, "Hi"),
+            ((0, -7), (9, 2))
+        );
+        assert_eq!(
+            approximate_layout_box("monospace", 10.0, "abc°"),
+            ((0, -6), (24, 2))
+        );
+        assert_eq!(
+            approximate_layout_box("no-such-font", 100.0, "é"),
+            ((0, -72), (56, 21))
+        );
+
+        // The metrics of the font are used when it can be loaded
+        let font = FontDesc::new("Arial", 12.0);
+        if let Ok(expected) = font.layout_box("Hello") {
+            assert_eq!(layout_box(&font, "Hello"), expected);
+        }
+    }
+}
@@ -2,13 +2,15 @@
 The SVG image drawing backend
 */
 
-use svg::node::element::{Circle, Line, Polyline, Rectangle, Text};
+use svg::node::element::{Circle, ClipPath, Group, Line, Polygon, Polyline, Rectangle, Text};
+use svg::node::Node;
 use svg::Document;
 
+use super::PresentOnDrop;
 use crate::drawing::backend::{BackendCoord, BackendStyle, DrawingBackend, DrawingErrorKind};
-use crate::style::{Color, FontDesc};
+use crate::style::{Color, FillRule, FontDesc, HAnchor, LineCap, LineJoin, TextLayout, VAnchor};
 
-use std::io::Error;
+use std::io::{Error, ErrorKind, Write};
 use std::path::Path;
 
 fn make_svg_color<C: Color>(color: &C) -> String {
@@ -20,11 +22,46 @@ fn make_svg_opacity<C: Color>(color: &C) -> String {
     return format!("{}", color.alpha());
 }
 
+fn set_svg_stroke<N: Node, S: BackendStyle>(node: &mut N, style: &S) {
+    node.assign("stroke-width", style.stroke_width());
+    if style.stroke_dash().iter().any(|&l| l > 0) {
+        let pattern: Vec<_> = style.stroke_dash().iter().map(u32::to_string).collect();
+        node.assign("stroke-dasharray", pattern.join(","));
+    }
+    match style.line_cap() {
+        LineCap::Butt => {}
+        LineCap::Round => node.assign("stroke-linecap", "round"),
+        LineCap::Square => node.assign("stroke-linecap", "square"),
+    }
+    match style.line_join() {
+        LineJoin::Miter => {}
+        LineJoin::Round => node.assign("stroke-linejoin", "round"),
+        LineJoin::Bevel => node.assign("stroke-linejoin", "bevel"),
+    }
+}
+
+/// Where the SVG document goes when the backend is presented
+enum Target<'a> {
+    /// Save the document to a file
+    File(&'a Path),
+    /// Write the document to a writer, which is released once the document is written
+    Writer(Option<Box<dyn Write + 'a>>),
+    /// Replace the content of a string with the document
+    String(&'a mut String),
+}
+
 /// The SVG image drawing backend
 pub struct SVGBackend<'a> {
-    path: &'a Path,
+    target: Target<'a>,
     size: (u32, u32),
     document: Option<Document>,
+    /// The clip regions defined in the document, the index of a region is the ID of its clip
+    /// path, so that each region is only defined once
+    clip_regions: Vec<(BackendCoord, BackendCoord)>,
+    /// The index of the current clip region
+    clip: Option<usize>,
+    /// The group of elements restricted by a clip region, and the index of the region
+    clip_group: Option<(usize, Group)>,
     saved: bool,
 }
 
@@ -35,15 +72,71 @@ impl<'a> SVGBackend<'a> {
         self.document = Some(op(temp.unwrap()));
     }
 
-    /// Create a new SVG drawing backend
-    pub fn new<T: AsRef<Path> + ?Sized>(path: &'a T, size: (u32, u32)) -> Self {
+    fn add_node<N: Node>(&mut self, node: N) {
+        let clip = match self.clip {
+            Some(clip) => clip,
+            None => {
+                self.close_clip_group();
+                return self.update_document(|d| d.add(node));
+            }
+        };
+        // The consecutive elements with the same clip region share a group
+        let group = match self.clip_group.take() {
+            Some((idx, group)) if idx == clip => group,
+            other => {
+                self.clip_group = other;
+                self.close_clip_group();
+                Group::new().set("clip-path", format!("url(#clip{})", clip))
+            }
+        };
+        self.clip_group = Some((clip, group.add(node)));
+    }
+
+    fn close_clip_group(&mut self) {
+        if let Some((_, group)) = self.clip_group.take() {
+            self.update_document(|d| d.add(group));
+        }
+    }
+
+    fn with_target(target: Target<'a>, size: (u32, u32)) -> Self {
         Self {
-            path: path.as_ref(),
+            target,
             size,
             document: Some(Document::new().set("viewBox", (0, 0, size.0, size.1))),
+            clip_regions: vec![],
+            clip: None,
+            clip_group: None,
             saved: false,
         }
     }
+
+    /// Create a new SVG drawing backend
+    pub fn new<T: AsRef<Path> + ?Sized>(path: &'a T, size: (u32, u32)) -> Self {
+        Self::with_target(Target::File(path.as_ref()), size)
+    }
+
+    /// Create a new SVG drawing backend which writes the document to a writer when it's
+    /// presented, for example to embed the image into a HTML page. A writer can only receive
+    /// one document, so the backend can only be presented once, and presenting it again is an
+    /// error. Use `with_string` to render the document more than once.
+    /// - `writer`: The writer the document is written to
+    /// - `size`: The size of the image
+    pub fn with_writer<W: Write + 'a>(writer: W, size: (u32, u32)) -> Self {
+        Self::with_target(Target::Writer(Some(Box::new(writer))), size)
+    }
+
+    /// Create a new SVG drawing backend which renders the document into a string. The content
+    /// of the string is replaced with the document each time the backend is presented.
+    /// - `buffer`: The string that receives the document
+    /// - `size`: The size of the image
+    pub fn with_string(buffer: &'a mut String, size: (u32, u32)) -> Self {
+        Self::with_target(Target::String(buffer), size)
+    }
+
+    /// Save the document and consume the backend
+    pub fn finish(mut self) -> Result<(), DrawingErrorKind<Error>> {
+        self.finish_present()
+    }
 }
 
 impl<'a> DrawingBackend for SVGBackend<'a> {
@@ -58,12 +151,55 @@ impl<'a> DrawingBackend for SVGBackend<'a> {
     }
 
     fn present(&mut self) -> Result<(), DrawingErrorKind<Error>> {
-        svg::save(self.path, self.document.as_ref().unwrap())
-            .map_err(DrawingErrorKind::DrawingError)?;
+        self.close_clip_group();
+        let document = self.document.as_ref().unwrap();
+        match self.target {
+            Target::File(path) => {
+                svg::save(path, document).map_err(DrawingErrorKind::DrawingError)?;
+            }
+            Target::Writer(ref mut writer) => {
+                let mut writer = writer.take().ok_or_else(|| {
+                    DrawingErrorKind::DrawingError(Error::new(
+                        ErrorKind::Other,
+                        "The SVG document has already been written",
+                    ))
+                })?;
+                svg::write(&mut writer, document)
+                    .and_then(|_| writer.flush())
+                    .map_err(DrawingErrorKind::DrawingError)?;
+            }
+            Target::String(ref mut buffer) => **buffer = document.to_string(),
+        }
         self.saved = true;
         Ok(())
     }
 
+    fn set_clip_region(
+        &mut self,
+        region: Option<(BackendCoord, BackendCoord)>,
+    ) -> Result<bool, DrawingErrorKind<Error>> {
+        self.clip = region.map(|region| {
+            if let Some(idx) = self.clip_regions.iter().position(|r| *r == region) {
+                return idx;
+            }
+            let (upper_left, bottom_right) = region;
+            let clip_path = ClipPath::new()
+                .set("id", format!("clip{}", self.clip_regions.len()))
+                .add(
+                    Rectangle::new()
+                        .set("x", upper_left.0)
+                        .set("y", upper_left.1)
+                        .set("width", bottom_right.0 - upper_left.0 + 1)
+                        .set("height", bottom_right.1 - upper_left.1 + 1),
+                );
+            self.close_clip_group();
+            self.update_document(|d| d.add(clip_path));
+            self.clip_regions.push(region);
+            self.clip_regions.len() - 1
+        });
+        Ok(true)
+    }
+
     fn draw_pixel<C: Color>(
         &mut self,
         point: BackendCoord,
@@ -77,7 +213,7 @@ impl<'a> DrawingBackend for SVGBackend<'a> {
             .set("stroke", "none")
             .set("opacity", make_svg_opacity(color))
             .set("fill", make_svg_color(color));
-        self.update_document(|d| d.add(node));
+        self.add_node(node);
         Ok(())
     }
 
@@ -87,14 +223,15 @@ impl<'a> DrawingBackend for SVGBackend<'a> {
         to: BackendCoord,
         style: &S,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let node = Line::new()
+        let mut node = Line::new()
             .set("x1", from.0)
             .set("y1", from.1)
             .set("x2", to.0)
             .set("y2", to.1)
             .set("opacity", make_svg_opacity(style.as_color()))
             .set("stroke", make_svg_color(style.as_color()));
-        self.update_document(|d| d.add(node));
+        set_svg_stroke(&mut node, style);
+        self.add_node(node);
         Ok(())
     }
 
@@ -116,6 +253,7 @@ impl<'a> DrawingBackend for SVGBackend<'a> {
                 .set("opacity", make_svg_opacity(style.as_color()))
                 .set("stroke", make_svg_color(style.as_color()))
                 .set("fill", "none");
+            set_svg_stroke(&mut node, style);
         } else {
             node = node
                 .set("opacity", make_svg_opacity(style.as_color()))
@@ -123,7 +261,7 @@ impl<'a> DrawingBackend for SVGBackend<'a> {
                 .set("stroke", "none");
         }
 
-        self.update_document(|d| d.add(node));
+        self.add_node(node);
         Ok(())
     }
 
@@ -132,7 +270,7 @@ impl<'a> DrawingBackend for SVGBackend<'a> {
         path: I,
         style: &S,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let node = Polyline::new()
+        let mut node = Polyline::new()
             .set("fill", "none")
             .set("opacity", make_svg_opacity(style.as_color()))
             .set("stroke", make_svg_color(style.as_color()))
@@ -143,7 +281,42 @@ impl<'a> DrawingBackend for SVGBackend<'a> {
                     s
                 }),
             );
-        self.update_document(|d| d.add(node));
+        set_svg_stroke(&mut node, style);
+        self.add_node(node);
+        Ok(())
+    }
+
+    fn draw_polygon<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        vert: I,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let mut node = Polygon::new()
+            .set("opacity", make_svg_opacity(style.as_color()))
+            .set(
+                "points",
+                vert.into_iter().fold(String::new(), |mut s, (x, y)| {
+                    s.push_str(&format!("{},{} ", x, y));
+                    s
+                }),
+            );
+
+        if !fill {
+            node = node
+                .set("stroke", make_svg_color(style.as_color()))
+                .set("fill", "none");
+            set_svg_stroke(&mut node, style);
+        } else {
+            node = node
+                .set("fill", make_svg_color(style.as_color()))
+                .set("stroke", "none");
+            if style.fill_rule() == FillRule::EvenOdd {
+                node = node.set("fill-rule", "evenodd");
+            }
+        }
+
+        self.add_node(node);
         Ok(())
     }
 
@@ -164,6 +337,7 @@ impl<'a> DrawingBackend for SVGBackend<'a> {
                 .set("opacity", make_svg_opacity(style.as_color()))
                 .set("stroke", make_svg_color(style.as_color()))
                 .set("fill", "none");
+            set_svg_stroke(&mut node, style);
         } else {
             node = node
                 .set("opacity", make_svg_opacity(style.as_color()))
@@ -171,7 +345,7 @@ impl<'a> DrawingBackend for SVGBackend<'a> {
                 .set("stroke", "none");
         }
 
-        self.update_document(|d| d.add(node));
+        self.add_node(node);
         Ok(())
     }
     fn draw_text<'b, C: Color>(
@@ -179,27 +353,127 @@ impl<'a> DrawingBackend for SVGBackend<'a> {
         text: &str,
         font: &FontDesc<'b>,
         pos: BackendCoord,
+        layout: &TextLayout,
         color: &C,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
         let context = svg::node::Text::new(text);
-        let ((_, b), (_, _)) = font.layout_box(text).map_err(DrawingErrorKind::FontError)?;
-        let node = Text::new()
+        let mut node = Text::new()
             .set("x", pos.0)
-            .set("y", pos.1 - b)
+            .set("y", pos.1)
             .set("font-famliy", font.get_name())
             .set("font-size", font.get_size())
             .set("opacity", make_svg_opacity(color))
-            .set("fill", make_svg_color(color))
-            .add(context);
-        self.update_document(|d| d.add(node));
+            .set("fill", make_svg_color(color));
+
+        match layout.h_anchor {
+            HAnchor::Left => {}
+            HAnchor::Center => node = node.set("text-anchor", "middle"),
+            HAnchor::Right => node = node.set("text-anchor", "end"),
+        }
+        match layout.v_anchor {
+            VAnchor::Top => node = node.set("dominant-baseline", "text-before-edge"),
+            VAnchor::Middle => node = node.set("dominant-baseline", "middle"),
+            VAnchor::Baseline => {}
+            VAnchor::Bottom => node = node.set("dominant-baseline", "text-after-edge"),
+        }
+        if layout.rotation != 0.0 {
+            // SVG rotates clockwise, while the text layout rotates counter-clockwise
+            node = node.set(
+                "transform",
+                format!("rotate({}, {}, {})", -layout.rotation, pos.0, pos.1),
+            );
+        }
+
+        self.add_node(node.add(context));
         Ok(())
     }
 }
 
+impl PresentOnDrop for SVGBackend<'_> {
+    fn is_saved(&self) -> bool {
+        self.saved
+    }
+
+    fn set_saved(&mut self) {
+        self.saved = true;
+    }
+}
+
 impl Drop for SVGBackend<'_> {
     fn drop(&mut self) {
-        if !self.saved {
-            self.present().expect("Unable to save the SVG image");
+        self.present_on_drop();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::drawing::take_drop_error;
+    use crate::style::RGBColor;
+
+    #[test]
+    fn test_render_to_string() {
+        let mut buffer = String::new();
+        {
+            let mut backend = SVGBackend::with_string(&mut buffer, (100, 50));
+            backend
+                .draw_line((0, 0), (100, 50), &RGBColor(255, 0, 0))
+                .unwrap();
+        }
+        assert!(buffer.starts_with("<svg"));
+        assert!(buffer.contains("viewBox=\"0 0 100 50\""));
+        assert!(buffer.contains("<line"));
+
+        let mut output = vec![];
+        {
+            let mut backend = SVGBackend::with_writer(&mut output, (100, 50));
+            backend.present().unwrap();
+            // The writer has the document already, so it doesn't get another one
+            backend.draw_pixel((0, 0), &RGBColor(0, 0, 255)).unwrap();
+            assert!(backend.present().is_err());
         }
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("<svg viewBox=\"0 0 100 50\""));
+        assert_eq!(output.matches("<svg").count(), 1);
+
+        // The document is written when the backend is dropped without being presented
+        let mut output = vec![];
+        drop(SVGBackend::with_writer(&mut output, (100, 50)));
+        assert_eq!(
+            String::from_utf8(output).unwrap().matches("<svg").count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_shared_clip_path() {
+        let mut buffer = String::new();
+        {
+            let mut backend = SVGBackend::with_string(&mut buffer, (100, 50));
+            let region = ((10, 10), (50, 40));
+            for &x in [0, 60].iter() {
+                backend.set_clip_region(Some(region)).unwrap();
+                backend
+                    .draw_circle((x, 20), 15, &RGBColor(255, 0, 0), true)
+                    .unwrap();
+                backend.set_clip_region(None).unwrap();
+            }
+            backend.set_clip_region(Some(((0, 0), (9, 9)))).unwrap();
+            backend.draw_pixel((5, 5), &RGBColor(0, 0, 255)).unwrap();
+        }
+        assert_eq!(buffer.matches("<clipPath").count(), 2);
+        assert_eq!(buffer.matches("url(#clip0)").count(), 1);
+        assert_eq!(buffer.matches("url(#clip1)").count(), 1);
+    }
+
+    #[test]
+    fn test_finish_error() {
+        let path = "/nonexistent-directory/plotters-test.svg";
+        assert!(SVGBackend::new(path, (4, 4)).finish().is_err());
+        assert!(take_drop_error().is_none());
+        // Dropping a backend which can't be saved must not panic, the error is recorded instead
+        drop(SVGBackend::new(path, (4, 4)));
+        assert!(take_drop_error().is_some());
+        assert!(take_drop_error().is_none());
     }
 }
@@ -0,0 +1,524 @@
+/*!
+The terminal drawing backend, which draws the chart with Unicode characters, so that it can be
+shown in a terminal or in a log file
+*/
+
+use super::PresentOnDrop;
+use crate::drawing::backend::{BackendCoord, BackendStyle, DrawingBackend, DrawingErrorKind};
+use crate::style::{Color, FontDesc, HAnchor, TextLayout};
+
+use std::io::{Error, Write};
+
+/// How much a color channel of a pixel must differ from its background for the pixel to be
+/// drawn. Light series colors such as yellow or light gray are drawn, while the light mesh lines
+/// of a chart, which cover most pixels at terminal resolutions, don't fill the character grid.
+const LIT_CONTRAST: u8 = 32;
+
+/// A color in red, green, blue order
+type Rgb = (u8, u8, u8);
+
+/// The characters used to draw the pixels
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CharMode {
+    /// Braille patterns, each character is a cell of 2x4 pixels
+    Braille,
+    /// Half blocks, each character is a cell of 1x2 pixels
+    HalfBlock,
+}
+
+impl CharMode {
+    /// The number of pixels in a character cell
+    fn cell_size(self) -> (u32, u32) {
+        match self {
+            CharMode::Braille => (2, 4),
+            CharMode::HalfBlock => (1, 2),
+        }
+    }
+}
+
+/// The escape codes used to color the characters
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColorMode {
+    /// No escape codes, the characters are shown in the default color of the terminal
+    Monochrome,
+    /// The 256 color palette of ANSI terminals
+    Ansi256,
+    /// 24-bit colors, which most modern terminals support
+    TrueColor,
+}
+
+impl ColorMode {
+    /// Get the escape code parameters which set the foreground or background color
+    fn sgr(self, (r, g, b): Rgb, background: bool) -> String {
+        let target = if background { 48 } else { 38 };
+        match self {
+            ColorMode::Monochrome => String::new(),
+            ColorMode::Ansi256 => format!("{};5;{}", target, ansi256(r, g, b)),
+            ColorMode::TrueColor => format!("{};2;{};{};{}", target, r, g, b),
+        }
+    }
+}
+
+/// Find the closest color of the 256 color palette, grays use the grayscale ramp
+fn ansi256(r: u8, g: u8, b: u8) -> u8 {
+    if r == g && g == b {
+        return match r {
+            0..=7 => 16,
+            249..=255 => 231,
+            v => 232 + ((v - 3) / 10).min(23),
+        };
+    }
+    // The levels of the color cube are 0, 95, 135, 175, 215 and 255
+    let level = |c: u8| match c {
+        0..=47 => 0,
+        48..=114 => 1,
+        c => (c - 35) / 40,
+    };
+    16 + 36 * level(r) + 6 * level(g) + level(b)
+}
+
+/// A character cell of the output, with its foreground and background color
+type Cell = (char, Option<Rgb>, Option<Rgb>);
+
+/// The terminal drawing backend. The pixels are drawn on a white canvas and grouped into
+/// character cells, a pixel is shown when its color differs noticeably from its background,
+/// which is the color of the latest opaque fill under it or the white canvas. Text is placed as
+/// real characters on top of the pixels, one character per cell regardless of the font size.
+///
+/// Each call of `present` writes the whole frame to the writer. The frames after the first one
+/// move the cursor up and erase the previous frame first, so that an animation or a live chart
+/// is redrawn in place.
+pub struct TerminalBackend<'a> {
+    writer: Box<dyn Write + 'a>,
+    /// The size of the output in characters
+    size: (u32, u32),
+    char_mode: CharMode,
+    color_mode: ColorMode,
+    /// The color of each pixel
+    pixels: Vec<Rgb>,
+    /// The background color of each pixel, which the color is compared with
+    background: Vec<Rgb>,
+    /// The text characters placed in the cells
+    text: Vec<Option<(char, Rgb)>>,
+    /// Flag indicates if a frame has been written, which the next frame replaces
+    written: bool,
+    saved: bool,
+}
+
+impl<'a> TerminalBackend<'a> {
+    /// Create a new terminal backend
+    /// - `writer`: The writer the frames are written to, for example `std::io::stdout()`
+    /// - `size`: The size of the output in characters, the size of the drawing in pixels depends
+    ///   on `char_mode`
+    /// - `char_mode`: The characters used to draw the pixels
+    /// - `color_mode`: The escape codes used to color the output
+    pub fn new<W: Write + 'a>(
+        writer: W,
+        size: (u32, u32),
+        char_mode: CharMode,
+        color_mode: ColorMode,
+    ) -> Self {
+        let (cw, ch) = char_mode.cell_size();
+        let cells = size.0 as usize * size.1 as usize;
+        Self {
+            writer: Box::new(writer),
+            size,
+            char_mode,
+            color_mode,
+            pixels: vec![(255, 255, 255); cells * (cw * ch) as usize],
+            background: vec![(255, 255, 255); cells * (cw * ch) as usize],
+            text: vec![None; cells],
+            written: false,
+            saved: false,
+        }
+    }
+
+    /// Write the frame and consume the backend
+    pub fn finish(mut self) -> Result<(), DrawingErrorKind<Error>> {
+        self.finish_present()
+    }
+
+    /// Get the color of a pixel, if it differs enough from the background to be drawn
+    fn lit_pixel(&self, x: u32, y: u32) -> Option<Rgb> {
+        let (w, _) = self.get_size();
+        let idx = (y * w + x) as usize;
+        let (color, background) = (self.pixels[idx], self.background[idx]);
+        let diff = |a: u8, b: u8| a.max(b) - a.min(b);
+        let contrast = diff(color.0, background.0)
+            .max(diff(color.1, background.1))
+            .max(diff(color.2, background.2));
+        if contrast >= LIT_CONTRAST {
+            Some(color)
+        } else {
+            None
+        }
+    }
+
+    /// Put a text character in the cell, or erase it. The cells outside of the output are
+    /// ignored.
+    fn set_text(&mut self, col: i32, row: i32, text: Option<(char, Rgb)>) {
+        if col >= 0 && row >= 0 && (col as u32) < self.size.0 && (row as u32) < self.size.1 {
+            self.text[(row as u32 * self.size.0 + col as u32) as usize] = text;
+        }
+    }
+
+    /// Build the character of a cell from its pixels
+    fn cell(&self, col: u32, row: u32) -> Cell {
+        let (cw, ch) = self.char_mode.cell_size();
+        let (x, y) = (col * cw, row * ch);
+        match self.char_mode {
+            CharMode::Braille => {
+                // The bit of each dot in a braille pattern, column by column
+                const DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+                let mut pattern = 0;
+                let mut sum = (0, 0, 0, 0);
+                for (dx, dots) in DOTS.iter().enumerate() {
+                    for (dy, bit) in dots.iter().enumerate() {
+                        if let Some((r, g, b)) = self.lit_pixel(x + dx as u32, y + dy as u32) {
+                            pattern |= bit;
+                            sum = (
+                                sum.0 + u32::from(r),
+                                sum.1 + u32::from(g),
+                                sum.2 + u32::from(b),
+                                sum.3 + 1,
+                            );
+                        }
+                    }
+                }
+                if pattern == 0 {
+                    return (' ', None, None);
+                }
+                let average = |c: u32| (c / sum.3) as u8;
+                let color = (average(sum.0), average(sum.1), average(sum.2));
+                let c = std::char::from_u32(0x2800 + pattern).unwrap_or(' ');
+                (c, Some(color), None)
+            }
+            CharMode::HalfBlock => match (self.lit_pixel(x, y), self.lit_pixel(x, y + 1)) {
+                (None, None) => (' ', None, None),
+                (Some(top), None) => ('\u{2580}', Some(top), None),
+                (None, Some(bottom)) => ('\u{2584}', Some(bottom), None),
+                (Some(top), Some(bottom)) => {
+                    if self.color_mode == ColorMode::Monochrome
+                        || self.color_mode.sgr(top, false) == self.color_mode.sgr(bottom, false)
+                    {
+                        ('\u{2588}', Some(top), None)
+                    } else {
+                        ('\u{2580}', Some(top), Some(bottom))
+                    }
+                }
+            },
+        }
+    }
+
+    /// Render the frame, one line per row of characters
+    fn render(&self) -> String {
+        let mut frame = String::new();
+        for row in 0..self.size.1 {
+            let mut cells: Vec<Cell> = (0..self.size.0)
+                .map(|col| match self.text[(row * self.size.0 + col) as usize] {
+                    Some((c, color)) => (c, Some(color), None),
+                    None => self.cell(col, row),
+                })
+                .collect();
+            while cells
+                .last()
+                .map_or(false, |cell| cell.0 == ' ' && cell.2.is_none())
+            {
+                cells.pop();
+            }
+
+            let mut current = (None, None);
+            for (c, fg, bg) in cells {
+                let colors = if self.color_mode == ColorMode::Monochrome || c == ' ' {
+                    (None, bg)
+                } else {
+                    (fg, bg)
+                };
+                if self.color_mode != ColorMode::Monochrome && colors != current {
+                    frame.push_str("\x1b[0");
+                    for (color, background) in [(colors.0, false), (colors.1, true)].iter() {
+                        if let Some(color) = color {
+                            frame.push(';');
+                            frame.push_str(&self.color_mode.sgr(*color, *background));
+                        }
+                    }
+                    frame.push('m');
+                    current = colors;
+                }
+                frame.push(c);
+            }
+            if current != (None, None) {
+                frame.push_str("\x1b[0m");
+            }
+            frame.push('\n');
+        }
+        frame
+    }
+}
+
+impl<'a> DrawingBackend for TerminalBackend<'a> {
+    type ErrorType = Error;
+
+    fn get_size(&self) -> (u32, u32) {
+        let (cw, ch) = self.char_mode.cell_size();
+        (self.size.0 * cw, self.size.1 * ch)
+    }
+
+    fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<Error>> {
+        Ok(())
+    }
+
+    fn present(&mut self) -> Result<(), DrawingErrorKind<Error>> {
+        let mut frame = String::new();
+        if self.written && self.size.1 > 0 {
+            // Move the cursor to the first line of the previous frame and erase it
+            frame.push_str(&format!("\x1b[{}A\x1b[J", self.size.1));
+        }
+        frame.push_str(&self.render());
+        self.writer
+            .write_all(frame.as_bytes())
+            .and_then(|_| self.writer.flush())
+            .map_err(DrawingErrorKind::DrawingError)?;
+        self.written = true;
+        self.saved = true;
+        Ok(())
+    }
+
+    fn draw_pixel<C: Color>(
+        &mut self,
+        point: BackendCoord,
+        color: &C,
+    ) -> Result<(), DrawingErrorKind<Error>> {
+        let (w, h) = self.get_size();
+        if point.0 < 0 || point.1 < 0 || point.0 as u32 >= w || point.1 as u32 >= h {
+            return Ok(());
+        }
+
+        let alpha = color.alpha().max(0.0).min(1.0);
+        let rgb = color.rgb();
+        let pixel = &mut self.pixels[(point.1 as u32 * w + point.0 as u32) as usize];
+        let blend =
+            |old: u8, new: u8| (f64::from(old) * (1.0 - alpha) + f64::from(new) * alpha) as u8;
+        *pixel = (
+            blend(pixel.0, rgb.0),
+            blend(pixel.1, rgb.1),
+            blend(pixel.2, rgb.2),
+        );
+        Ok(())
+    }
+
+    fn draw_rect<S: BackendStyle>(
+        &mut self,
+        upper_left: BackendCoord,
+        bottom_right: BackendCoord,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let (x0, x1) = (
+            upper_left.0.min(bottom_right.0),
+            upper_left.0.max(bottom_right.0),
+        );
+        let (y0, y1) = (
+            upper_left.1.min(bottom_right.1),
+            upper_left.1.max(bottom_right.1),
+        );
+        if !fill {
+            return self.draw_polygon(vec![(x0, y0), (x1, y0), (x1, y1), (x0, y1)], style, false);
+        }
+
+        // The text in the cells which are covered by an opaque fill is erased, so that filling
+        // the background starts a new frame, and the fill is the background of its pixels
+        let (w, h) = self.get_size();
+        if style.as_color().alpha() >= 1.0 {
+            let (cw, ch) = self.char_mode.cell_size();
+            let (cw, ch) = (cw as i32, ch as i32);
+            for row in (y0 + ch - 1).div_euclid(ch)..(y1 + 1).div_euclid(ch) {
+                for col in (x0 + cw - 1).div_euclid(cw)..(x1 + 1).div_euclid(cw) {
+                    self.set_text(col, row, None);
+                }
+            }
+            for y in y0.max(0)..=y1.min(h as i32 - 1) {
+                for x in x0.max(0)..=x1.min(w as i32 - 1) {
+                    self.background[(y as u32 * w + x as u32) as usize] = style.as_color().rgb();
+                }
+            }
+        }
+
+        for y in y0.max(0)..=y1.min(h as i32 - 1) {
+            for x in x0.max(0)..=x1.min(w as i32 - 1) {
+                self.draw_pixel((x, y), style.as_color())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn draw_text<'b, C: Color>(
+        &mut self,
+        text: &str,
+        _font: &FontDesc<'b>,
+        pos: BackendCoord,
+        layout: &TextLayout,
+        color: &C,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let (cw, ch) = self.char_mode.cell_size();
+        let (col, row) = (pos.0.div_euclid(cw as i32), pos.1.div_euclid(ch as i32));
+        let chars: Vec<char> = text.chars().collect();
+        // The number of characters before the anchor point in reading order
+        let before = match layout.h_anchor {
+            HAnchor::Left => 0,
+            HAnchor::Center => chars.len() as i32 / 2,
+            HAnchor::Right => chars.len() as i32,
+        };
+
+        // Rotated text is written vertically once it's closer to vertical than horizontal
+        let sin = layout.rotation.to_radians().sin();
+        let rgb = color.rgb();
+        for (idx, c) in chars.into_iter().enumerate() {
+            let offset = idx as i32 - before;
+            if sin > std::f64::consts::FRAC_1_SQRT_2 {
+                self.set_text(col, row - offset, Some((c, rgb)));
+            } else if sin < -std::f64::consts::FRAC_1_SQRT_2 {
+                self.set_text(col, row + offset, Some((c, rgb)));
+            } else {
+                self.set_text(col + offset, row, Some((c, rgb)));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PresentOnDrop for TerminalBackend<'_> {
+    fn is_saved(&self) -> bool {
+        self.saved
+    }
+
+    fn set_saved(&mut self) {
+        self.saved = true;
+    }
+}
+
+impl Drop for TerminalBackend<'_> {
+    fn drop(&mut self) {
+        self.present_on_drop();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::style::{Mixable, RGBColor, VAnchor};
+
+    #[test]
+    fn test_braille_frame() {
+        let mut output = vec![];
+        {
+            let mut backend = TerminalBackend::new(
+                &mut output,
+                (4, 2),
+                CharMode::Braille,
+                ColorMode::Monochrome,
+            );
+            assert_eq!(backend.get_size(), (8, 8));
+            backend
+                .draw_line((0, 0), (7, 0), &RGBColor(0, 0, 0))
+                .unwrap();
+            backend.draw_pixel((1, 7), &RGBColor(0, 0, 0)).unwrap();
+            // Too light to be drawn
+            backend
+                .draw_pixel((4, 4), &RGBColor(230, 230, 230))
+                .unwrap();
+            backend
+                .draw_text(
+                    "ab",
+                    &FontDesc::new("Arial", 12.0),
+                    (8, 4),
+                    &TextLayout::new(HAnchor::Right, VAnchor::Top),
+                    &RGBColor(0, 0, 0),
+                )
+                .unwrap();
+            backend.finish().unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\u{2809}\u{2809}\u{2809}\u{2809}\n\u{2880} ab\n"
+        );
+    }
+
+    #[test]
+    fn test_colored_half_blocks() {
+        let mut output = vec![];
+        {
+            let mut backend = TerminalBackend::new(
+                &mut output,
+                (3, 1),
+                CharMode::HalfBlock,
+                ColorMode::TrueColor,
+            );
+            backend
+                .draw_rect((0, 0), (2, 1), &RGBColor(255, 255, 255), true)
+                .unwrap();
+            backend.draw_pixel((0, 0), &RGBColor(255, 0, 0)).unwrap();
+            backend.draw_pixel((1, 0), &RGBColor(255, 0, 0)).unwrap();
+            backend.draw_pixel((1, 1), &RGBColor(0, 0, 255)).unwrap();
+            backend.finish().unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\x1b[0;38;2;255;0;0m\u{2580}\x1b[0;38;2;255;0;0;48;2;0;0;255m\u{2580}\x1b[0m\n"
+        );
+        assert_eq!(ansi256(255, 0, 0), 196);
+        assert_eq!(ansi256(128, 128, 128), 244);
+    }
+
+    #[test]
+    fn test_contrast_with_background() {
+        let mut output = vec![];
+        {
+            let mut backend = TerminalBackend::new(
+                &mut output,
+                (3, 1),
+                CharMode::HalfBlock,
+                ColorMode::Monochrome,
+            );
+            // Yellow and light gray differ from the white canvas, the light mesh color doesn't
+            backend.draw_pixel((0, 0), &RGBColor(255, 255, 0)).unwrap();
+            backend
+                .draw_pixel((0, 1), &RGBColor(211, 211, 211))
+                .unwrap();
+            backend
+                .draw_pixel((1, 0), &RGBColor(0, 0, 0).mix(0.1))
+                .unwrap();
+            // On a black background, black pixels aren't drawn but white ones are
+            backend
+                .draw_rect((2, 0), (2, 1), &RGBColor(0, 0, 0), true)
+                .unwrap();
+            backend
+                .draw_pixel((2, 1), &RGBColor(255, 255, 255))
+                .unwrap();
+            backend.finish().unwrap();
+        }
+        assert_eq!(String::from_utf8(output).unwrap(), "\u{2588} \u{2584}\n");
+    }
+
+    #[test]
+    fn test_redraw_frame() {
+        let mut output = vec![];
+        {
+            let mut backend = TerminalBackend::new(
+                &mut output,
+                (2, 2),
+                CharMode::HalfBlock,
+                ColorMode::Monochrome,
+            );
+            backend.draw_pixel((0, 0), &RGBColor(0, 0, 0)).unwrap();
+            backend.present().unwrap();
+            backend.draw_pixel((1, 3), &RGBColor(0, 0, 0)).unwrap();
+            backend.present().unwrap();
+        }
+        // The second frame moves the cursor up over the first one and erases it
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\u{2580}\n\n\x1b[2A\x1b[J\u{2580}\n \u{2584}\n"
+        );
+    }
+}
@@ -0,0 +1,534 @@
+/*!
+The geometric clipping of the shapes drawn on a drawing area.
+
+Fills and hairline strokes are clipped geometrically before they are passed to the underlying
+backend. Wide or dashed strokes, circles and text which are partially visible are clipped by the
+clip region of the backend if it's supported, otherwise they are rasterized and clipped pixel by
+pixel. A wide stroke is clipped after it's stroked, so it doesn't spill over the clip edge.
+*/
+use super::backend::{BackendCoord, BackendStyle, DrawingBackend, DrawingErrorKind};
+use super::rasterizer;
+use crate::style::{Color, FontDesc, TextLayout};
+
+/// How a bounding box is related to the clip rectangle
+#[derive(PartialEq)]
+enum Visibility {
+    Inside,
+    Outside,
+    Partial,
+}
+
+/// The clip rectangle, both corners are inclusive
+#[derive(Clone, Copy)]
+struct ClipRect {
+    upper_left: BackendCoord,
+    bottom_right: BackendCoord,
+}
+
+impl ClipRect {
+    fn contains(&self, (x, y): BackendCoord) -> bool {
+        self.upper_left.0 <= x
+            && x <= self.bottom_right.0
+            && self.upper_left.1 <= y
+            && y <= self.bottom_right.1
+    }
+
+    fn classify_box(&self, upper_left: BackendCoord, bottom_right: BackendCoord) -> Visibility {
+        if self.contains(upper_left) && self.contains(bottom_right) {
+            Visibility::Inside
+        } else if bottom_right.0 < self.upper_left.0
+            || bottom_right.1 < self.upper_left.1
+            || upper_left.0 > self.bottom_right.0
+            || upper_left.1 > self.bottom_right.1
+        {
+            Visibility::Outside
+        } else {
+            Visibility::Partial
+        }
+    }
+
+    /// Clip a line segment with the Liang-Barsky algorithm
+    fn clip_segment(
+        &self,
+        from: BackendCoord,
+        to: BackendCoord,
+    ) -> Option<(BackendCoord, BackendCoord)> {
+        let (x0, y0) = (f64::from(from.0), f64::from(from.1));
+        let (dx, dy) = (f64::from(to.0) - x0, f64::from(to.1) - y0);
+        let (mut t0, mut t1) = (0.0, 1.0);
+
+        for &(p, q) in [
+            (-dx, x0 - f64::from(self.upper_left.0)),
+            (dx, f64::from(self.bottom_right.0) - x0),
+            (-dy, y0 - f64::from(self.upper_left.1)),
+            (dy, f64::from(self.bottom_right.1) - y0),
+        ]
+        .iter()
+        {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None;
+                }
+                continue;
+            }
+            let r = q / p;
+            if p < 0.0 {
+                if r > t1 {
+                    return None;
+                }
+                t0 = r.max(t0);
+            } else {
+                if r < t0 {
+                    return None;
+                }
+                t1 = r.min(t1);
+            }
+        }
+
+        let at = |t: f64| ((x0 + dx * t).round() as i32, (y0 + dy * t).round() as i32);
+        Some((at(t0), at(t1)))
+    }
+
+    /// Clip a path, which gives the visible parts of the path
+    fn clip_path(&self, path: &[BackendCoord]) -> Vec<Vec<BackendCoord>> {
+        let mut ret = vec![];
+        let mut current: Vec<BackendCoord> = vec![];
+
+        for seg in path.windows(2) {
+            match self.clip_segment(seg[0], seg[1]) {
+                Some((from, to)) => {
+                    if from != seg[0] || current.last() != Some(&from) {
+                        if current.len() > 1 {
+                            ret.push(std::mem::take(&mut current));
+                        }
+                        current = vec![from];
+                    }
+                    current.push(to);
+                    if to != seg[1] {
+                        ret.push(std::mem::take(&mut current));
+                    }
+                }
+                None => {
+                    if current.len() > 1 {
+                        ret.push(std::mem::take(&mut current));
+                    }
+                    current.clear();
+                }
+            }
+        }
+
+        if current.len() > 1 {
+            ret.push(current);
+        }
+
+        ret
+    }
+
+    /// Clip a polygon with the Sutherland-Hodgman algorithm
+    fn clip_polygon(&self, vert: &[BackendCoord]) -> Vec<BackendCoord> {
+        let mut points: Vec<_> = vert
+            .iter()
+            .map(|&(x, y)| (f64::from(x), f64::from(y)))
+            .collect();
+
+        let (x0, y0) = (f64::from(self.upper_left.0), f64::from(self.upper_left.1));
+        let (x1, y1) = (
+            f64::from(self.bottom_right.0),
+            f64::from(self.bottom_right.1),
+        );
+
+        for edge in 0..4 {
+            let inside = |p: (f64, f64)| match edge {
+                0 => p.0 >= x0,
+                1 => p.0 <= x1,
+                2 => p.1 >= y0,
+                _ => p.1 <= y1,
+            };
+            let intersect = |a, b| match edge {
+                0 => intersect_x(a, b, x0),
+                1 => intersect_x(a, b, x1),
+                2 => intersect_y(a, b, y0),
+                _ => intersect_y(a, b, y1),
+            };
+
+            let input = std::mem::take(&mut points);
+            for (idx, &cur) in input.iter().enumerate() {
+                let prev = input[(idx + input.len() - 1) % input.len()];
+                if inside(cur) {
+                    if !inside(prev) {
+                        points.push(intersect(prev, cur));
+                    }
+                    points.push(cur);
+                } else if inside(prev) {
+                    points.push(intersect(prev, cur));
+                }
+            }
+        }
+
+        points
+            .into_iter()
+            .map(|(x, y)| (x.round() as i32, y.round() as i32))
+            .collect()
+    }
+}
+
+fn intersect_x(a: (f64, f64), b: (f64, f64), x: f64) -> (f64, f64) {
+    (x, a.1 + (b.1 - a.1) * (x - a.0) / (b.0 - a.0))
+}
+
+fn intersect_y(a: (f64, f64), b: (f64, f64), y: f64) -> (f64, f64) {
+    (a.0 + (b.0 - a.0) * (y - a.1) / (b.1 - a.1), y)
+}
+
+/// The backend adapter that clips everything drawn on it to a rectangle
+pub(crate) struct ClippedBackend<'a, DB: DrawingBackend> {
+    backend: &'a mut DB,
+    clip: ClipRect,
+}
+
+impl<'a, DB: DrawingBackend> ClippedBackend<'a, DB> {
+    /// Create a new clipped backend
+    /// - `backend`: The underlying backend
+    /// - `upper_left`, `bottom_right`: The corners of the clip rectangle, both are inclusive
+    pub(crate) fn new(
+        backend: &'a mut DB,
+        upper_left: BackendCoord,
+        bottom_right: BackendCoord,
+    ) -> Self {
+        Self {
+            backend,
+            clip: ClipRect {
+                upper_left,
+                bottom_right,
+            },
+        }
+    }
+
+    /// Try to draw with the clip region of the underlying backend.
+    /// Returns false if the backend doesn't support clipping, thus nothing has been drawn.
+    fn draw_with_clip_region<F>(&mut self, draw: F) -> Result<bool, DrawingErrorKind<DB::ErrorType>>
+    where
+        F: FnOnce(&mut DB) -> Result<(), DrawingErrorKind<DB::ErrorType>>,
+    {
+        if !self
+            .backend
+            .set_clip_region(Some((self.clip.upper_left, self.clip.bottom_right)))?
+        {
+            return Ok(false);
+        }
+        let result = draw(self.backend);
+        self.backend.set_clip_region(None)?;
+        result.map(|_| true)
+    }
+
+    /// Classify the bounding box of a stroked path, including the part of the stroke which
+    /// extends beyond the path
+    fn classify_stroke<S: BackendStyle>(&self, path: &[BackendCoord], style: &S) -> Visibility {
+        if path.is_empty() {
+            return Visibility::Outside;
+        }
+        // A miter join may reach twice the stroke width away from the path before it's beveled
+        let extent = if rasterizer::is_hairline(style) {
+            0
+        } else {
+            style.stroke_width() as i32 * 2 + 1
+        };
+        let (upper_left, bottom_right) = path.iter().fold(
+            ((i32::MAX, i32::MAX), (i32::MIN, i32::MIN)),
+            |(ul, br), &(x, y)| ((ul.0.min(x), ul.1.min(y)), (br.0.max(x), br.1.max(y))),
+        );
+        self.clip.classify_box(
+            (upper_left.0 - extent, upper_left.1 - extent),
+            (bottom_right.0 + extent, bottom_right.1 + extent),
+        )
+    }
+
+    /// Draw a stroke which is partially visible. A hairline is clipped geometrically, while a
+    /// wide or dashed stroke is drawn by the backend with its clip region, or stroked by the
+    /// rasterizer and clipped pixel by pixel.
+    /// - `path`: The path of the stroke
+    /// - `closed`: If the last point should be connected to the first point
+    /// - `draw`: Draws the unclipped stroke on the backend
+    fn draw_partial_stroke<S: BackendStyle, F>(
+        &mut self,
+        path: &[BackendCoord],
+        closed: bool,
+        style: &S,
+        draw: F,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>>
+    where
+        F: FnOnce(&mut DB) -> Result<(), DrawingErrorKind<DB::ErrorType>>,
+    {
+        if rasterizer::is_hairline(style) {
+            let mut path = path.to_vec();
+            if closed {
+                if let Some(&first) = path.first() {
+                    path.push(first);
+                }
+            }
+            for part in self.clip.clip_path(&path) {
+                self.backend.draw_path(part, style)?;
+            }
+            return Ok(());
+        }
+        if !self.draw_with_clip_region(draw)? {
+            rasterizer::stroke_path(self, path, closed, style)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, DB: DrawingBackend> DrawingBackend for ClippedBackend<'a, DB> {
+    type ErrorType = DB::ErrorType;
+
+    fn get_size(&self) -> (u32, u32) {
+        self.backend.get_size()
+    }
+
+    fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        self.backend.ensure_prepared()
+    }
+
+    fn present(&mut self) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        self.backend.present()
+    }
+
+    fn set_clip_region(
+        &mut self,
+        region: Option<(BackendCoord, BackendCoord)>,
+    ) -> Result<bool, DrawingErrorKind<DB::ErrorType>> {
+        self.backend.set_clip_region(region)
+    }
+
+    fn draw_pixel<C: Color>(
+        &mut self,
+        point: BackendCoord,
+        color: &C,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        if self.clip.contains(point) {
+            return self.backend.draw_pixel(point, color);
+        }
+        Ok(())
+    }
+
+    fn draw_line<S: BackendStyle>(
+        &mut self,
+        from: BackendCoord,
+        to: BackendCoord,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        match self.classify_stroke(&[from, to], style) {
+            Visibility::Inside => self.backend.draw_line(from, to, style),
+            Visibility::Outside => Ok(()),
+            Visibility::Partial => self
+                .draw_partial_stroke(&[from, to], false, style, |b| b.draw_line(from, to, style)),
+        }
+    }
+
+    fn draw_rect<S: BackendStyle>(
+        &mut self,
+        upper_left: BackendCoord,
+        bottom_right: BackendCoord,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        let (upper_left, bottom_right) = (
+            (
+                upper_left.0.min(bottom_right.0),
+                upper_left.1.min(bottom_right.1),
+            ),
+            (
+                upper_left.0.max(bottom_right.0),
+                upper_left.1.max(bottom_right.1),
+            ),
+        );
+
+        let outline = [
+            upper_left,
+            (bottom_right.0, upper_left.1),
+            bottom_right,
+            (upper_left.0, bottom_right.1),
+        ];
+        let visibility = if fill {
+            self.clip.classify_box(upper_left, bottom_right)
+        } else {
+            self.classify_stroke(&outline, style)
+        };
+
+        match visibility {
+            Visibility::Inside => self
+                .backend
+                .draw_rect(upper_left, bottom_right, style, fill),
+            Visibility::Outside => Ok(()),
+            Visibility::Partial if fill => self.backend.draw_rect(
+                (
+                    upper_left.0.max(self.clip.upper_left.0),
+                    upper_left.1.max(self.clip.upper_left.1),
+                ),
+                (
+                    bottom_right.0.min(self.clip.bottom_right.0),
+                    bottom_right.1.min(self.clip.bottom_right.1),
+                ),
+                style,
+                fill,
+            ),
+            Visibility::Partial => self.draw_partial_stroke(&outline, true, style, |b| {
+                b.draw_rect(upper_left, bottom_right, style, fill)
+            }),
+        }
+    }
+
+    fn draw_path<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        path: I,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        let path: Vec<_> = path.into_iter().collect();
+        match self.classify_stroke(&path, style) {
+            Visibility::Inside => self.backend.draw_path(path, style),
+            Visibility::Outside => Ok(()),
+            Visibility::Partial => self.draw_partial_stroke(&path, false, style, |b| {
+                b.draw_path(path.iter().copied(), style)
+            }),
+        }
+    }
+
+    fn draw_polygon<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        vert: I,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        let vert: Vec<_> = vert.into_iter().collect();
+        if fill {
+            if vert.iter().all(|&p| self.clip.contains(p)) {
+                return self.backend.draw_polygon(vert, style, fill);
+            }
+            let vert = self.clip.clip_polygon(&vert);
+            if vert.len() > 2 {
+                self.backend.draw_polygon(vert, style, fill)?;
+            }
+            return Ok(());
+        }
+        match self.classify_stroke(&vert, style) {
+            Visibility::Inside => self.backend.draw_polygon(vert, style, fill),
+            Visibility::Outside => Ok(()),
+            Visibility::Partial => self.draw_partial_stroke(&vert, true, style, |b| {
+                b.draw_polygon(vert.iter().copied(), style, fill)
+            }),
+        }
+    }
+
+    fn draw_circle<S: BackendStyle>(
+        &mut self,
+        center: BackendCoord,
+        radius: u32,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        // Leave space for the stroke and the anti-aliasing pixels
+        let extent = (radius + style.stroke_width() / 2 + 1) as i32;
+        match self.clip.classify_box(
+            (center.0 - extent, center.1 - extent),
+            (center.0 + extent, center.1 + extent),
+        ) {
+            Visibility::Inside => self.backend.draw_circle(center, radius, style, fill),
+            Visibility::Outside => Ok(()),
+            Visibility::Partial => {
+                if !self.draw_with_clip_region(|b| b.draw_circle(center, radius, style, fill))? {
+                    rasterizer::draw_circle(self, center, radius, style, fill)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn draw_text<'b, C: Color>(
+        &mut self,
+        text: &str,
+        font: &FontDesc<'b>,
+        pos: BackendCoord,
+        layout: &TextLayout,
+        color: &C,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        // The text which can't be measured, for example because the font isn't installed, is
+        // passed to the backend unclipped, since some backends don't need the font to draw it
+        let (upper_left, bottom_right) = match layout.bounding_box(font, text, pos) {
+            Ok(bounds) => bounds,
+            Err(_) => return self.backend.draw_text(text, font, pos, layout, color),
+        };
+        match self.clip.classify_box(upper_left, bottom_right) {
+            Visibility::Inside => self.backend.draw_text(text, font, pos, layout, color),
+            Visibility::Outside => Ok(()),
+            Visibility::Partial => {
+                if !self.draw_with_clip_region(|b| b.draw_text(text, font, pos, layout, color))? {
+                    rasterizer::draw_text(self, text, font, pos, layout, color)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CLIP: ClipRect = ClipRect {
+        upper_left: (10, 10),
+        bottom_right: (50, 50),
+    };
+
+    #[test]
+    fn test_clip_segment() {
+        assert_eq!(
+            CLIP.clip_segment((20, 20), (30, 40)),
+            Some(((20, 20), (30, 40)))
+        );
+        assert_eq!(
+            CLIP.clip_segment((0, 30), (100, 30)),
+            Some(((10, 30), (50, 30)))
+        );
+        assert_eq!(
+            CLIP.clip_segment((0, 0), (60, 60)),
+            Some(((10, 10), (50, 50)))
+        );
+        assert_eq!(CLIP.clip_segment((0, 0), (100, 0)), None);
+        assert_eq!(CLIP.clip_segment((60, 0), (100, 40)), None);
+    }
+
+    #[test]
+    fn test_clip_path() {
+        assert_eq!(
+            CLIP.clip_path(&[(20, 20), (30, 20), (30, 80), (40, 80), (40, 20)]),
+            vec![vec![(20, 20), (30, 20), (30, 50)], vec![(40, 50), (40, 20)]]
+        );
+    }
+
+    #[test]
+    fn test_clip_wide_stroke() {
+        use super::rasterizer::test::PixelSet;
+        use crate::style::{RGBColor, ShapeStyle};
+        use std::collections::HashSet;
+
+        let style = ShapeStyle::from(&RGBColor(0, 0, 0)).stroke_width(9);
+        let mut pixels = PixelSet(HashSet::new());
+        {
+            let mut backend = ClippedBackend::new(&mut pixels, (10, 10), (50, 50));
+            backend.draw_line((0, 30), (100, 30), &style).unwrap();
+            // The path is inside, but the stroke crosses the clip edge
+            backend.draw_path(vec![(20, 12), (40, 12)], &style).unwrap();
+        }
+        assert!(pixels.0.iter().all(|&p| CLIP.contains(p)));
+        assert!(pixels.0.contains(&(10, 26)) && pixels.0.contains(&(50, 34)));
+        assert!(pixels.0.contains(&(30, 10)) && pixels.0.contains(&(30, 16)));
+    }
+
+    #[test]
+    fn test_clip_polygon() {
+        assert_eq!(
+            CLIP.clip_polygon(&[(0, 30), (30, 0), (30, 30)]),
+            vec![(10, 30), (10, 20), (20, 10), (30, 10), (30, 30)]
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use super::area::{DrawingArea, DrawingAreaErrorKind};
+use super::backend_impl::{BitMapBackend, PixelFormat};
+use crate::coord::Shift;
+use image::{ImageError, Rgb, RgbImage};
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+/// The color of the pixels that differ from the golden image in the diff image
+const MISMATCH_COLOR: Rgb<u8> = Rgb { data: [255, 0, 0] };
+
+/// The error of a visual regression check
+#[derive(Debug)]
+pub enum GoldenImageError {
+    /// The drawing function failed
+    DrawingError(DrawingAreaErrorKind<ImageError>),
+    /// The golden image can't be loaded or the diff image can't be saved
+    ImageError(ImageError),
+    /// The rendered image has a different size from the golden image
+    SizeMismatch {
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+    /// Too many pixels differ from the golden image, the diff image has been saved to `diff_path`
+    Mismatch {
+        diff_pixels: usize,
+        max_diff_pixels: usize,
+        diff_path: PathBuf,
+    },
+}
+
+impl Display for GoldenImageError {
+    fn fmt(&self, fmt: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            GoldenImageError::DrawingError(e) => write!(fmt, "drawing error: {}", e),
+            GoldenImageError::ImageError(e) => write!(fmt, "image error: {}", e),
+            GoldenImageError::SizeMismatch { expected, actual } => write!(
+                fmt,
+                "The image is {}x{}, but the golden image is {}x{}",
+                actual.0, actual.1, expected.0, expected.1
+            ),
+            GoldenImageError::Mismatch {
+                diff_pixels,
+                max_diff_pixels,
+                diff_path,
+            } => write!(
+                fmt,
+                "{} pixels differ from the golden image, at most {} are allowed, see {}",
+                diff_pixels,
+                max_diff_pixels,
+                diff_path.display()
+            ),
+        }
+    }
+}
+
+impl Error for GoldenImageError {}
+
+/// A visual regression check, which compares a rendered bitmap with a stored golden image.
+///
+/// The drawing is rendered into an in-memory buffer, so nothing is written unless the check
+/// fails. In that case, a diff image is saved, where the mismatched pixels are red and the rest
+/// of the rendered image is faded.
+///
+/// ```rust,no_run
+/// use plotters::prelude::*;
+/// GoldenImage::new("tests/golden/chart.png")
+///     .channel_tolerance(2)
+///     .max_diff_pixels(10)
+///     .check((640, 480), |root| {
+///         root.fill(&White)?;
+///         let mut chart = ChartBuilder::on(root).build_ranged(0..10, 0..10)?;
+///         chart.configure_mesh().draw()
+///     })
+///     .unwrap();
+/// ```
+pub struct GoldenImage<'a> {
+    path: &'a Path,
+    diff_path: PathBuf,
+    channel_tolerance: u8,
+    max_diff_pixels: usize,
+}
+
+impl<'a> GoldenImage<'a> {
+    /// Create a new check against the golden image. By default, every channel of every pixel
+    /// must match exactly, and the diff image is saved next to the golden image with the
+    /// `.diff.png` extension.
+    /// - `path`: The path of the golden image
+    pub fn new<T: AsRef<Path> + ?Sized>(path: &'a T) -> Self {
+        let path = path.as_ref();
+        Self {
+            path,
+            diff_path: path.with_extension("diff.png"),
+            channel_tolerance: 0,
+            max_diff_pixels: 0,
+        }
+    }
+
+    /// Set how much each of the red, green and blue channels of a pixel may differ from the
+    /// golden image before the pixel is considered different
+    pub fn channel_tolerance(&mut self, value: u8) -> &mut Self {
+        self.channel_tolerance = value;
+        self
+    }
+
+    /// Set how many pixels may differ from the golden image before the check fails
+    pub fn max_diff_pixels(&mut self, value: usize) -> &mut Self {
+        self.max_diff_pixels = value;
+        self
+    }
+
+    /// Set the path where the diff image is saved when the check fails
+    pub fn diff_path<T: AsRef<Path> + ?Sized>(&mut self, path: &T) -> &mut Self {
+        self.diff_path = path.as_ref().to_path_buf();
+        self
+    }
+
+    /// Render the drawing into a bitmap and compare it with the golden image
+    /// - `size`: The size of the bitmap
+    /// - `draw`: The function that draws on the root drawing area of the bitmap
+    pub fn check<F>(&self, size: (u32, u32), draw: F) -> Result<(), GoldenImageError>
+    where
+        F: FnOnce(
+            &DrawingArea<BitMapBackend, Shift>,
+        ) -> Result<(), DrawingAreaErrorKind<ImageError>>,
+    {
+        let mut pixels = vec![0; size.0 as usize * size.1 as usize * 3];
+        {
+            let backend = BitMapBackend::with_buffer(&mut pixels, size, PixelFormat::Rgb)
+                .map_err(GoldenImageError::ImageError)?;
+            let root: DrawingArea<_, _> = backend.into();
+            draw(&root).map_err(GoldenImageError::DrawingError)?;
+            root.present().map_err(GoldenImageError::DrawingError)?;
+        }
+        // The buffer has exactly the size of the image, so this can't fail
+        let image = RgbImage::from_raw(size.0, size.1, pixels).unwrap();
+        self.compare(&image)
+    }
+
+    /// Compare an image which is already rendered with the golden image
+    /// - `image`: The rendered image
+    pub fn compare(&self, image: &RgbImage) -> Result<(), GoldenImageError> {
+        let golden = image::open(self.path)
+            .map_err(GoldenImageError::ImageError)?
+            .to_rgb();
+        if golden.dimensions() != image.dimensions() {
+            return Err(GoldenImageError::SizeMismatch {
+                expected: golden.dimensions(),
+                actual: image.dimensions(),
+            });
+        }
+
+        let tolerance = self.channel_tolerance;
+        let mut diff = RgbImage::new(image.width(), image.height());
+        let mut diff_pixels = 0;
+        for ((actual, expected), out) in image.pixels().zip(golden.pixels()).zip(diff.pixels_mut())
+        {
+            let differs = actual
+                .data
+                .iter()
+                .zip(&expected.data)
+                .any(|(a, e)| a.max(e) - a.min(e) > tolerance);
+            *out = if differs {
+                diff_pixels += 1;
+                MISMATCH_COLOR
+            } else {
+                // Fade the matched pixels, so the mismatches stand out while the drawing is
+                // still recognizable
+                let mut faded = actual.data;
+                faded.iter_mut().for_each(|c| *c = 191 + *c / 4);
+                Rgb { data: faded }
+            };
+        }
+
+        if diff_pixels <= self.max_diff_pixels {
+            return Ok(());
+        }
+        diff.save(&self.diff_path)
+            .map_err(|e| GoldenImageError::ImageError(ImageError::IoError(e)))?;
+        Err(GoldenImageError::Mismatch {
+            diff_pixels,
+            max_diff_pixels: self.max_diff_pixels,
+            diff_path: self.diff_path.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::style::RGBColor;
+
+    fn draw_square(
+        root: &DrawingArea<BitMapBackend, Shift>,
+        color: RGBColor,
+    ) -> Result<(), DrawingAreaErrorKind<ImageError>> {
+        root.fill(&RGBColor(255, 255, 255))?;
+        root.draw_pixel((1, 1), &color)?;
+        root.draw_pixel((2, 2), &color)
+    }
+
+    #[test]
+    fn test_golden_image() {
+        let dir = std::env::temp_dir().join(format!("plotters-golden-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let golden = dir.join("square.png");
+        {
+            let root: DrawingArea<_, _> = BitMapBackend::new(&golden, (4, 4)).into();
+            draw_square(&root, RGBColor(0, 0, 0)).unwrap();
+        }
+
+        let mut check = GoldenImage::new(&golden);
+        check
+            .check((4, 4), |root| draw_square(root, RGBColor(0, 0, 0)))
+            .unwrap();
+        assert!(!dir.join("square.diff.png").exists());
+
+        match check.check((4, 4), |root| draw_square(root, RGBColor(3, 0, 0))) {
+            Err(GoldenImageError::Mismatch {
+                diff_pixels,
+                diff_path,
+                ..
+            }) => {
+                assert_eq!(diff_pixels, 2);
+                let diff = image::open(&diff_path).unwrap().to_rgb();
+                assert_eq!(diff.get_pixel(1, 1), &MISMATCH_COLOR);
+                assert_eq!(diff.get_pixel(0, 0).data, [254, 254, 254]);
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+        check
+            .channel_tolerance(3)
+            .check((4, 4), |root| draw_square(root, RGBColor(3, 0, 0)))
+            .unwrap();
+        check
+            .channel_tolerance(0)
+            .max_diff_pixels(2)
+            .check((4, 4), |root| draw_square(root, RGBColor(3, 0, 0)))
+            .unwrap();
+
+        assert!(matches!(
+            check.check((4, 3), |root| draw_square(root, RGBColor(0, 0, 0))),
+            Err(GoldenImageError::SizeMismatch { .. })
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
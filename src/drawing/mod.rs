@@ -9,14 +9,39 @@ On the top of drawing backend, one or more drawing area can be defined and diffe
 system can be applied to the drawing areas. And the drawing area implement the high-level drawing
 interface, which draws an element.
 
-Currently we have two backend implemented:
-
-- `BitMapBackend`: The backend that creates bitmap, this is based on `image` crate
-- `SVGBackend`: The backend that creates SVG image, based on `svg` crate.
+Currently we have the following backends implemented:
+
+- `BitMapBackend`: The backend that creates bitmap, this is based on `image` crate. The bitmap
+  can be saved to a file, encoded to any writer, or drawn into a buffer owned by the caller.
+  With the `gif_backend` feature, each frame can be appended to an animated GIF.
+- `SVGBackend`: The backend that creates SVG image, based on `svg` crate. The document can be
+  saved to a file, written to any writer, or rendered into a string.
+- `PDFBackend`: The backend that creates a single-page PDF document with native vector operators,
+  which is only available with the `pdf` feature.
+- `EPSBackend`: The backend that creates an Encapsulated PostScript figure, which is only
+  available with the `eps` feature.
+- `TerminalBackend`: The backend that draws with braille or block characters and writes each
+  frame to any writer, which is only available with the `terminal` feature.
+- `RecordingBackend`: The backend that records the drawing calls as a `DisplayList`, which can be
+  replayed on any other backend, optionally scaled. With the `serde` feature, the display list
+  can be serialized.
+
+The backends that save or write their output, which are all of the above except for
+`RecordingBackend`, present themselves when they are dropped without being presented. Drop can't
+return an error, and panicking in it would abort a program that is already unwinding, so an error
+on this path is recorded, and `take_drop_error` returns it. Call `finish` on the backend, or
+`DrawingArea::into_result`, to save the output and handle the error right away.
+
+With the `bitmap` feature, `GoldenImage` renders a drawing into an in-memory bitmap and compares
+it with a stored golden image, which catches rendering regressions in tests.
 
 */
 mod area;
 mod backend_impl;
+mod clipping;
+#[cfg(all(not(target_arch = "wasm32"), feature = "image"))]
+mod golden;
+mod rasterizer;
 
 pub mod backend;
 
@@ -24,4 +49,7 @@ pub use area::{DrawingArea, DrawingAreaErrorKind};
 
 pub use backend_impl::*;
 
+#[cfg(all(not(target_arch = "wasm32"), feature = "image"))]
+pub use golden::{GoldenImage, GoldenImageError};
+
 pub use backend::DrawingBackend;
@@ -0,0 +1,770 @@
+/*!
+The default rasterizer, which is used by the provided methods of `DrawingBackend` to draw
+shapes that the backend doesn't support natively, such as wide or dashed strokes.
+*/
+use super::backend::{BackendCoord, BackendStyle, DrawingBackend, DrawingErrorKind};
+use crate::style::{Color, FillRule, FontDesc, LineCap, LineJoin, Mixable, RGBColor, TextLayout};
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+
+/// A point in the backend coordinate with sub-pixel precision
+type FloatCoord = (f64, f64);
+
+/// The miter length to stroke width ratio at which a miter join is converted into a bevel,
+/// this is the same default value SVG uses
+const MITER_LIMIT: f64 = 4.0;
+
+/// Check if the style describes a solid stroke of a single pixel, which is drawn by
+/// the plain line algorithm
+pub(crate) fn is_hairline<S: BackendStyle>(style: &S) -> bool {
+    style.stroke_width() <= 1 && style.stroke_dash().iter().all(|&l| l == 0)
+}
+
+fn to_backend_coord((x, y): FloatCoord) -> BackendCoord {
+    (x.round() as i32, y.round() as i32)
+}
+
+fn direction(from: FloatCoord, to: FloatCoord) -> FloatCoord {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let len = (dx * dx + dy * dy).sqrt();
+    (dx / len, dy / len)
+}
+
+/// Fill a polygon with the given fill rule. The scanlines are sampled at the integer
+/// y coordinates and each pixel whose center is inside the polygon is filled.
+pub(crate) fn fill_polygon<DB: DrawingBackend + ?Sized, C: Color>(
+    backend: &mut DB,
+    vertices: &[FloatCoord],
+    rule: FillRule,
+    color: &C,
+) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+    if vertices.len() < 3 {
+        return Ok(());
+    }
+
+    let (min_y, max_y) = vertices
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(min, max), &(_, y)| {
+            (min.min(y), max.max(y))
+        });
+
+    let mut crossings = vec![];
+
+    for y in min_y.ceil() as i32..=max_y.floor() as i32 {
+        let fy = f64::from(y);
+        crossings.clear();
+
+        for (idx, &a) in vertices.iter().enumerate() {
+            let b = vertices[(idx + 1) % vertices.len()];
+            let (low, high, winding) = if a.1 < b.1 { (a, b, 1) } else { (b, a, -1) };
+            if fy < low.1 || fy >= high.1 {
+                continue;
+            }
+            let x = low.0 + (fy - low.1) * (high.0 - low.0) / (high.1 - low.1);
+            crossings.push((x, winding));
+        }
+
+        crossings.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        let mut winding = 0;
+        for idx in 0..crossings.len() {
+            winding += crossings[idx].1;
+            let inside = match rule {
+                FillRule::NonZero => winding != 0,
+                FillRule::EvenOdd => winding % 2 != 0,
+            };
+            if inside && idx + 1 < crossings.len() {
+                let left = crossings[idx].0.ceil() as i32;
+                let right = crossings[idx + 1].0.ceil() as i32 - 1;
+                if left <= right {
+                    backend.draw_line((left, y), (right, y), color)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The pixels covered by the pieces of a stroke, each with the largest opacity it's drawn with.
+/// A wide stroke is made of segments, joins and caps which overlap each other, so drawing the
+/// pieces one by one would blend a translucent color twice where they overlap.
+struct Coverage {
+    size: (u32, u32),
+    pixels: HashMap<BackendCoord, f64>,
+}
+
+impl DrawingBackend for Coverage {
+    type ErrorType = Infallible;
+
+    fn get_size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<Infallible>> {
+        Ok(())
+    }
+
+    fn present(&mut self) -> Result<(), DrawingErrorKind<Infallible>> {
+        Ok(())
+    }
+
+    fn draw_pixel<C: Color>(
+        &mut self,
+        point: BackendCoord,
+        color: &C,
+    ) -> Result<(), DrawingErrorKind<Infallible>> {
+        if color.alpha() > 0.0 {
+            let alpha = self.pixels.entry(point).or_insert(0.0);
+            *alpha = alpha.max(color.alpha());
+        }
+        Ok(())
+    }
+}
+
+impl Coverage {
+    /// Draw the pieces into a coverage, and then draw each covered pixel once with the color.
+    /// The neighbor pixels of the same opacity in a row are drawn as a single line.
+    fn draw<DB: DrawingBackend + ?Sized, C: Color, F>(
+        backend: &mut DB,
+        color: &C,
+        draw_pieces: F,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>>
+    where
+        F: FnOnce(&mut Coverage) -> Result<(), DrawingErrorKind<Infallible>>,
+    {
+        let mut coverage = Coverage {
+            size: backend.get_size(),
+            pixels: HashMap::new(),
+        };
+        if let Err(DrawingErrorKind::FontError(e)) = draw_pieces(&mut coverage) {
+            return Err(DrawingErrorKind::FontError(e));
+        }
+
+        // The opacity is already part of the coverage, and a concrete color type keeps the
+        // nested generic calls of the default `draw_line` finite
+        let (r, g, b) = color.rgb();
+        let rgb = RGBColor(r, g, b);
+        let mut pixels: Vec<_> = coverage.pixels.into_iter().collect();
+        pixels.sort_by_key(|&((x, y), _)| (y, x));
+
+        let mut start = 0;
+        while start < pixels.len() {
+            let ((x, y), alpha) = pixels[start];
+            let mut end = start + 1;
+            while end < pixels.len()
+                && pixels[end].0 == (x + (end - start) as i32, y)
+                && (pixels[end].1 - alpha).abs() < 1e-9
+            {
+                end += 1;
+            }
+            let color = rgb.mix(alpha);
+            if end - start == 1 {
+                backend.draw_pixel((x, y), &color)?;
+            } else {
+                backend.draw_line((x, y), (x + (end - start - 1) as i32, y), &color)?;
+            }
+            start = end;
+        }
+
+        Ok(())
+    }
+}
+
+/// Split the polyline into the drawn parts of the dash pattern
+fn split_dashes(points: &[FloatCoord], pattern: &[u32]) -> Vec<Vec<FloatCoord>> {
+    // Like SVG, a pattern with odd number of entries is repeated to yield an even number
+    let repeat = if pattern.len() % 2 == 1 { 2 } else { 1 };
+    let pattern: Vec<f64> = pattern
+        .iter()
+        .cycle()
+        .take(pattern.len() * repeat)
+        .map(|&l| f64::from(l))
+        .collect();
+
+    let mut ret = vec![];
+    let mut current = vec![points[0]];
+    let mut idx = 0;
+    let mut left = pattern[0];
+    let mut drawing = true;
+
+    for seg in points.windows(2) {
+        let (mut from, to) = (seg[0], seg[1]);
+        let mut len = ((to.0 - from.0).powi(2) + (to.1 - from.1).powi(2)).sqrt();
+        let dir = direction(from, to);
+
+        while len > left {
+            let split = (from.0 + dir.0 * left, from.1 + dir.1 * left);
+            if drawing {
+                if current.last() != Some(&split) {
+                    current.push(split);
+                }
+                ret.push(std::mem::take(&mut current));
+            } else {
+                current = vec![split];
+            }
+            len -= left;
+            from = split;
+            idx = (idx + 1) % pattern.len();
+            left = pattern[idx];
+            drawing = !drawing;
+        }
+
+        left -= len;
+        if drawing {
+            current.push(to);
+        }
+    }
+
+    if drawing && current.len() > 1 {
+        ret.push(current);
+    }
+
+    ret
+}
+
+fn draw_join<DB: DrawingBackend + ?Sized, S: BackendStyle>(
+    backend: &mut DB,
+    vertex: FloatCoord,
+    (in_dir, out_dir): (FloatCoord, FloatCoord),
+    half_width: f64,
+    style: &S,
+) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+    let cross = in_dir.0 * out_dir.1 - in_dir.1 * out_dir.0;
+    if cross.abs() < 1e-9 {
+        return Ok(());
+    }
+
+    // The gap we need to fill is on the opposite side of the turn
+    let side = if cross > 0.0 { -half_width } else { half_width };
+    let a = (vertex.0 - in_dir.1 * side, vertex.1 + in_dir.0 * side);
+    let b = (vertex.0 - out_dir.1 * side, vertex.1 + out_dir.0 * side);
+
+    let cos_half = ((1.0 + in_dir.0 * out_dir.0 + in_dir.1 * out_dir.1) / 2.0).sqrt();
+
+    match style.line_join() {
+        LineJoin::Round => backend.draw_circle(
+            to_backend_coord(vertex),
+            half_width as u32,
+            style.as_color(),
+            true,
+        ),
+        LineJoin::Miter if cos_half > 1.0 / MITER_LIMIT => {
+            let t = ((b.0 - a.0) * out_dir.1 - (b.1 - a.1) * out_dir.0) / cross;
+            let miter = (a.0 + in_dir.0 * t, a.1 + in_dir.1 * t);
+            fill_polygon(
+                backend,
+                &[vertex, a, miter, b],
+                FillRule::NonZero,
+                style.as_color(),
+            )
+        }
+        _ => fill_polygon(
+            backend,
+            &[vertex, a, b],
+            FillRule::NonZero,
+            style.as_color(),
+        ),
+    }
+}
+
+fn stroke_polyline<DB: DrawingBackend + ?Sized, S: BackendStyle>(
+    backend: &mut DB,
+    points: &[FloatCoord],
+    closed: bool,
+    style: &S,
+) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+    let mut points = points.to_vec();
+    points.dedup();
+
+    if points.len() < 2 {
+        return Ok(());
+    }
+
+    if style.stroke_width() <= 1 {
+        for seg in points.windows(2) {
+            backend.draw_line(
+                to_backend_coord(seg[0]),
+                to_backend_coord(seg[1]),
+                style.as_color(),
+            )?;
+        }
+        return Ok(());
+    }
+
+    let half_width = f64::from(style.stroke_width()) / 2.0;
+    let last = points.len() - 2;
+
+    for idx in 0..=last {
+        let (mut from, mut to) = (points[idx], points[idx + 1]);
+        let dir = direction(from, to);
+        if !closed && style.line_cap() == LineCap::Square {
+            if idx == 0 {
+                from = (from.0 - dir.0 * half_width, from.1 - dir.1 * half_width);
+            }
+            if idx == last {
+                to = (to.0 + dir.0 * half_width, to.1 + dir.1 * half_width);
+            }
+        }
+        let (nx, ny) = (-dir.1 * half_width, dir.0 * half_width);
+        fill_polygon(
+            backend,
+            &[
+                (from.0 + nx, from.1 + ny),
+                (to.0 + nx, to.1 + ny),
+                (to.0 - nx, to.1 - ny),
+                (from.0 - nx, from.1 - ny),
+            ],
+            FillRule::NonZero,
+            style.as_color(),
+        )?;
+    }
+
+    for idx in 1..=last {
+        let dirs = (
+            direction(points[idx - 1], points[idx]),
+            direction(points[idx], points[idx + 1]),
+        );
+        draw_join(backend, points[idx], dirs, half_width, style)?;
+    }
+
+    if closed {
+        let dirs = (
+            direction(points[last], points[last + 1]),
+            direction(points[0], points[1]),
+        );
+        draw_join(backend, points[0], dirs, half_width, style)?;
+    } else if style.line_cap() == LineCap::Round {
+        for &end in [points[0], points[last + 1]].iter() {
+            backend.draw_circle(
+                to_backend_coord(end),
+                half_width as u32,
+                style.as_color(),
+                true,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn stroke_float_path<DB: DrawingBackend + ?Sized, S: BackendStyle>(
+    backend: &mut DB,
+    mut points: Vec<FloatCoord>,
+    closed: bool,
+    style: &S,
+) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+    if points.is_empty() {
+        return Ok(());
+    }
+
+    if closed {
+        points.push(points[0]);
+    }
+
+    if style.as_color().alpha() <= 0.0 {
+        return Ok(());
+    }
+
+    Coverage::draw(backend, style.as_color(), |coverage| {
+        if style.stroke_dash().iter().all(|&l| l == 0) {
+            return stroke_polyline(coverage, &points, closed, style);
+        }
+
+        for dash in split_dashes(&points, style.stroke_dash()) {
+            stroke_polyline(coverage, &dash, false, style)?;
+        }
+
+        Ok(())
+    })
+}
+
+/// Stroke a path with the width, dash pattern, line cap and line join defined by the style
+/// - `closed`: If the last point should be connected to the first point
+pub(crate) fn stroke_path<DB: DrawingBackend + ?Sized, S: BackendStyle>(
+    backend: &mut DB,
+    path: &[BackendCoord],
+    closed: bool,
+    style: &S,
+) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+    let points = path
+        .iter()
+        .map(|&(x, y)| (f64::from(x), f64::from(y)))
+        .collect();
+    stroke_float_path(backend, points, closed, style)
+}
+
+/// Stroke the outline of a circle with the width and dash pattern defined by the style
+pub(crate) fn stroke_circle<DB: DrawingBackend + ?Sized, S: BackendStyle>(
+    backend: &mut DB,
+    center: BackendCoord,
+    radius: u32,
+    style: &S,
+) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+    let (cx, cy) = (f64::from(center.0), f64::from(center.1));
+    let radius = f64::from(radius);
+
+    if style.stroke_dash().iter().any(|&l| l > 0) {
+        let n = (radius * 2.0).max(16.0) as u32;
+        let points = (0..n)
+            .map(|i| {
+                let angle = std::f64::consts::PI * 2.0 * f64::from(i) / f64::from(n);
+                (cx + radius * angle.cos(), cy + radius * angle.sin())
+            })
+            .collect();
+        return stroke_float_path(backend, points, true, style);
+    }
+
+    let half_width = f64::from(style.stroke_width()) / 2.0;
+    let (outer, inner) = (radius + half_width, (radius - half_width).max(0.0));
+
+    for dy in -outer.floor() as i32..=outer.floor() as i32 {
+        let fy = f64::from(dy);
+        let outer_x = (outer * outer - fy * fy).sqrt().round() as i32;
+        if fy.abs() < inner {
+            let inner_x = (inner * inner - fy * fy).sqrt().round() as i32;
+            if inner_x < outer_x {
+                let y = center.1 + dy;
+                backend.draw_line(
+                    (center.0 - outer_x, y),
+                    (center.0 - inner_x - 1, y),
+                    style.as_color(),
+                )?;
+                backend.draw_line(
+                    (center.0 + inner_x + 1, y),
+                    (center.0 + outer_x, y),
+                    style.as_color(),
+                )?;
+            }
+        } else {
+            backend.draw_line(
+                (center.0 - outer_x, center.1 + dy),
+                (center.0 + outer_x, center.1 + dy),
+                style.as_color(),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Draw a circle, this is the default implementation of `DrawingBackend::draw_circle`
+pub(crate) fn draw_circle<DB: DrawingBackend + ?Sized, S: BackendStyle>(
+    backend: &mut DB,
+    center: BackendCoord,
+    radius: u32,
+    style: &S,
+    fill: bool,
+) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+    if !fill && !is_hairline(style) {
+        return stroke_circle(backend, center, radius, style);
+    }
+
+    //let range = ((radius + 3) / 4) as i32..=(2 * radius - radius / 4) as i32;
+    let min = (f64::from(radius) * (1.0 - (2f64).sqrt() / 2.0)).ceil() as i32;
+    let max = (f64::from(radius) * (1.0 + (2f64).sqrt() / 2.0)).floor() as i32;
+
+    let range = min..=max;
+
+    let (up, down) = (
+        range.start() + center.1 - radius as i32,
+        range.end() + center.1 - radius as i32,
+    );
+
+    for dy in range {
+        let dy = dy - radius as i32;
+        let y = center.1 + dy;
+
+        let lx = (f64::from(radius) * f64::from(radius)
+            - (f64::from(dy) * f64::from(dy)).max(1e-5))
+        .sqrt();
+
+        let left = center.0 - lx.floor() as i32;
+        let right = center.0 + lx.floor() as i32;
+
+        let v = lx - lx.floor();
+
+        let x = center.0 + dy;
+        let top = center.1 - lx.floor() as i32;
+        let bottom = center.1 + lx.floor() as i32;
+
+        if fill {
+            backend.draw_line((left, y), (right, y), style.as_color())?;
+            backend.draw_line((x, top), (x, up), style.as_color())?;
+            backend.draw_line((x, down), (x, bottom), style.as_color())?;
+        } else {
+            backend.draw_pixel((left, y), &style.as_color().mix(1.0 - v))?;
+            backend.draw_pixel((right, y), &style.as_color().mix(1.0 - v))?;
+
+            backend.draw_pixel((x, top), &style.as_color().mix(1.0 - v))?;
+            backend.draw_pixel((x, bottom), &style.as_color().mix(1.0 - v))?;
+        }
+
+        backend.draw_pixel((left - 1, y), &style.as_color().mix(v))?;
+        backend.draw_pixel((right + 1, y), &style.as_color().mix(v))?;
+        backend.draw_pixel((x, top - 1), &style.as_color().mix(v))?;
+        backend.draw_pixel((x, bottom + 1), &style.as_color().mix(v))?;
+    }
+
+    Ok(())
+}
+
+/// Draw a text with the font rasterizer, this is the default implementation of
+/// `DrawingBackend::draw_text`
+pub(crate) fn draw_text<DB: DrawingBackend + ?Sized, C: Color>(
+    backend: &mut DB,
+    text: &str,
+    font: &FontDesc,
+    pos: BackendCoord,
+    layout: &TextLayout,
+    color: &C,
+) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+    let (ax, ay) = layout
+        .anchor_offset(font, text)
+        .map_err(DrawingErrorKind::FontError)?;
+
+    if layout.rotation != 0.0 {
+        return draw_rotated_text(backend, text, font, pos, (ax, ay), layout.rotation, color);
+    }
+
+    let origin = (pos.0 - ax.round() as i32, pos.1 - ay.round() as i32);
+    match font.draw(text, origin, |x, y, v| {
+        backend.draw_pixel((x as i32, y as i32), &color.mix(f64::from(v)))
+    }) {
+        Ok(drawing_result) => drawing_result,
+        Err(font_error) => Err(DrawingErrorKind::FontError(font_error)),
+    }
+}
+
+/// Draw a text rotated around an anchor point. The glyphs are rendered into a coverage buffer
+/// first, which is then sampled for each pixel that is covered by the rotated text, so that
+/// there are no gaps between the rotated pixels.
+/// - `pos`: The position of the anchor point in the backend coordinate
+/// - `anchor`: The anchor point relative to the position the unrotated text is drawn from
+/// - `angle`: The rotation in degrees, counter-clockwise
+fn draw_rotated_text<DB: DrawingBackend + ?Sized, C: Color>(
+    backend: &mut DB,
+    text: &str,
+    font: &FontDesc,
+    pos: BackendCoord,
+    anchor: FloatCoord,
+    angle: f64,
+    color: &C,
+) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+    let ((min_x, min_y), (max_x, max_y)) =
+        font.layout_box(text).map_err(DrawingErrorKind::FontError)?;
+    let (width, height) = ((max_x - min_x).max(0), (max_y - min_y).max(0));
+    if width == 0 || height == 0 {
+        return Ok(());
+    }
+    // The anchor point relative to the upper left corner of the coverage buffer
+    let anchor = (anchor.0 - f64::from(min_x), anchor.1);
+
+    let mut coverage = vec![0.0f32; (width * height) as usize];
+    font.draw(text, (-min_x, 0), |x, y, v| {
+        if x < width && y < height {
+            coverage[(y * width + x) as usize] = v;
+        }
+        Ok::<(), ()>(())
+    })
+    .map_err(DrawingErrorKind::FontError)?
+    .ok();
+
+    let (sin, cos) = angle.to_radians().sin_cos();
+    let corners = [
+        (-anchor.0, -anchor.1),
+        (f64::from(width) - anchor.0, -anchor.1),
+        (-anchor.0, f64::from(height) - anchor.1),
+        (f64::from(width) - anchor.0, f64::from(height) - anchor.1),
+    ];
+    let (mut left, mut top, mut right, mut bottom) = (f64::MAX, f64::MAX, f64::MIN, f64::MIN);
+    for &(u, v) in corners.iter() {
+        let (dx, dy) = (u * cos + v * sin, v * cos - u * sin);
+        left = left.min(dx);
+        top = top.min(dy);
+        right = right.max(dx);
+        bottom = bottom.max(dy);
+    }
+
+    for dy in top.floor() as i32..=bottom.ceil() as i32 {
+        for dx in left.floor() as i32..=right.ceil() as i32 {
+            let (fx, fy) = (f64::from(dx) + 0.5, f64::from(dy) + 0.5);
+            let u = fx * cos - fy * sin + anchor.0;
+            let v = fx * sin + fy * cos + anchor.1;
+            if u < 0.0 || v < 0.0 || u >= f64::from(width) || v >= f64::from(height) {
+                continue;
+            }
+            let alpha = coverage[v as usize * width as usize + u as usize];
+            if alpha > 0.0 {
+                backend.draw_pixel((pos.0 + dx, pos.1 + dy), &color.mix(f64::from(alpha)))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// A backend which only records the pixels that have been drawn
+    pub(crate) struct PixelSet(pub(crate) HashSet<BackendCoord>);
+
+    impl DrawingBackend for PixelSet {
+        type ErrorType = std::io::Error;
+        fn get_size(&self) -> (u32, u32) {
+            (100, 100)
+        }
+        fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+            Ok(())
+        }
+        fn present(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+            Ok(())
+        }
+        fn draw_pixel<C: Color>(
+            &mut self,
+            point: BackendCoord,
+            color: &C,
+        ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+            if color.alpha() > 0.0 {
+                self.0.insert(point);
+            }
+            Ok(())
+        }
+    }
+
+    /// A backend which blends the drawn colors onto white, and records the opacity of each pixel
+    struct Opacity(HashMap<BackendCoord, f64>);
+
+    impl DrawingBackend for Opacity {
+        type ErrorType = std::io::Error;
+        fn get_size(&self) -> (u32, u32) {
+            (100, 100)
+        }
+        fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+            Ok(())
+        }
+        fn present(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+            Ok(())
+        }
+        fn draw_pixel<C: Color>(
+            &mut self,
+            point: BackendCoord,
+            color: &C,
+        ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+            let opacity = self.0.entry(point).or_insert(0.0);
+            *opacity += (1.0 - *opacity) * color.alpha();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_translucent_stroke() {
+        use crate::style::{RGBColor, ShapeStyle};
+
+        let color = RGBColor(0, 0, 0).mix(0.5);
+        for &join in [LineJoin::Miter, LineJoin::Round, LineJoin::Bevel].iter() {
+            let style = Into::<ShapeStyle>::into(&color)
+                .stroke_width(10)
+                .line_join(join)
+                .line_cap(LineCap::Round);
+            let mut pixels = Opacity(HashMap::new());
+            stroke_path(&mut pixels, &[(10, 50), (50, 50), (50, 90)], false, &style).unwrap();
+
+            // The join and the caps are drawn as opaque as the middle of a segment
+            let middle = pixels.0[&(30, 50)];
+            assert!((middle - 0.5).abs() < 1e-9);
+            assert!((pixels.0[&(50, 50)] - middle).abs() < 1e-9);
+            assert!((pixels.0[&(10, 50)] - middle).abs() < 1e-9);
+            assert!(pixels.0.values().all(|&opacity| opacity <= middle + 1e-9));
+        }
+    }
+
+    #[test]
+    fn test_fill_rule() {
+        let star: Vec<_> = (0..5)
+            .map(|i| {
+                let angle = std::f64::consts::PI * (f64::from(i) * 0.8 - 0.5);
+                (50.0 + 40.0 * angle.cos(), 50.0 + 40.0 * angle.sin())
+            })
+            .collect();
+
+        let mut non_zero = PixelSet(HashSet::new());
+        fill_polygon(
+            &mut non_zero,
+            &star,
+            FillRule::NonZero,
+            &crate::style::Black,
+        )
+        .unwrap();
+        assert!(non_zero.0.contains(&(50, 50)));
+        assert!(non_zero.0.contains(&(50, 15)));
+
+        let mut even_odd = PixelSet(HashSet::new());
+        fill_polygon(
+            &mut even_odd,
+            &star,
+            FillRule::EvenOdd,
+            &crate::style::Black,
+        )
+        .unwrap();
+        assert!(!even_odd.0.contains(&(50, 50)));
+        assert!(even_odd.0.contains(&(50, 15)));
+    }
+
+    #[test]
+    fn test_split_dashes() {
+        let dashes = split_dashes(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)], &[4, 2]);
+
+        assert_eq!(
+            dashes,
+            vec![
+                vec![(0.0, 0.0), (4.0, 0.0)],
+                vec![(6.0, 0.0), (10.0, 0.0)],
+                vec![(10.0, 2.0), (10.0, 6.0)],
+                vec![(10.0, 8.0), (10.0, 10.0)],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_dashes_odd_pattern() {
+        let dashes = split_dashes(&[(0.0, 0.0), (9.0, 0.0)], &[3]);
+
+        assert_eq!(
+            dashes,
+            vec![vec![(0.0, 0.0), (3.0, 0.0)], vec![(6.0, 0.0), (9.0, 0.0)]]
+        );
+    }
+
+    #[test]
+    fn test_text_layout() {
+        use crate::style::{HAnchor, VAnchor};
+
+        let font = FontDesc::new("Arial", 20.0);
+        let color = crate::style::RGBColor(0, 0, 0);
+
+        for &(h, v, rotation) in [
+            (HAnchor::Left, VAnchor::Top, 0.0),
+            (HAnchor::Center, VAnchor::Middle, 90.0),
+            (HAnchor::Right, VAnchor::Baseline, 30.0),
+            (HAnchor::Center, VAnchor::Bottom, -45.0),
+        ]
+        .iter()
+        {
+            let layout = TextLayout::new(h, v).rotate(rotation);
+            let mut pixels = PixelSet(HashSet::new());
+            draw_text(&mut pixels, "plotters", &font, (50, 50), &layout, &color).unwrap();
+
+            let ((x0, y0), (x1, y1)) = layout.bounding_box(&font, "plotters", (50, 50)).unwrap();
+            assert!(!pixels.0.is_empty());
+            assert!(pixels
+                .0
+                .iter()
+                .all(|&(x, y)| x0 <= x && x <= x1 && y0 <= y && y <= y1));
+        }
+    }
+}
@@ -70,7 +70,44 @@ impl<'a, Coord: 'a> Drawable for Path<'a, Coord> {
         points: I,
         backend: &mut DB,
     ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
-        backend.draw_path(points, &Box::new(self.style.color))
+        backend.draw_path(points, &self.style)
+    }
+}
+
+/// A polygon element, the last vertex is connected to the first one
+pub struct Polygon<'a, Coord> {
+    points: Vec<Coord>,
+    style: ShapeStyle<'a>,
+}
+
+impl<'a, Coord> Polygon<'a, Coord> {
+    /// Create a new polygon
+    /// - `points`: The vertices of the polygon
+    /// - `style`: The shape style, a filled style fills the polygon with the style's fill rule
+    /// - returns the created element
+    pub fn new<P: Into<Vec<Coord>>, S: Into<ShapeStyle<'a>>>(points: P, style: S) -> Self {
+        Self {
+            points: points.into(),
+            style: style.into(),
+        }
+    }
+}
+
+impl<'b, 'a, Coord: 'a> PointCollection<'a, Coord> for &'a Polygon<'b, Coord> {
+    type Borrow = &'a Coord;
+    type IntoIter = &'a [Coord];
+    fn point_iter(self) -> &'a [Coord] {
+        &self.points
+    }
+}
+
+impl<'a, Coord: 'a> Drawable for Polygon<'a, Coord> {
+    fn draw<DB: DrawingBackend, I: Iterator<Item = BackendCoord>>(
+        &self,
+        points: I,
+        backend: &mut DB,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        backend.draw_polygon(points, &self.style, self.style.filled)
     }
 }
 
@@ -125,7 +162,7 @@ impl<'a, Coord: 'a> Drawable for Rectangle<'a, Coord> {
                 b.1 -= self.margin.1 as i32;
                 a.0 += self.margin.2 as i32;
                 b.0 -= self.margin.3 as i32;
-                backend.draw_rect(a, b, &Box::new(self.style.color), self.style.filled)
+                backend.draw_rect(a, b, &self.style, self.style.filled)
             }
             _ => Ok(()),
         }
@@ -142,7 +179,8 @@ pub struct Text<'a, Coord> {
 impl<'a, Coord> Text<'a, Coord> {
     /// Create a new text element
     /// - `text`: The text for the element
-    /// - `points`: The upper left conner for the text element
+    /// - `points`: The position of the text, which is the upper left corner unless the text
+    ///   style has another layout
     /// - `style`: The text style
     /// - Return the newly created text element
     pub fn new<T: AsRef<str>, S: Into<TextStyle<'a>>>(
@@ -173,7 +211,13 @@ impl<'a, Coord: 'a> Drawable for Text<'a, Coord> {
         backend: &mut DB,
     ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
         if let Some(a) = points.next() {
-            return backend.draw_text(self.text, self.style.font, a, &Box::new(self.style.color));
+            return backend.draw_text(
+                self.text,
+                self.style.font,
+                a,
+                &self.style.layout,
+                &Box::new(self.style.color),
+            );
         }
         Ok(())
     }
@@ -216,12 +260,7 @@ impl<'a, Coord: 'a> Drawable for Circle<'a, Coord> {
         backend: &mut DB,
     ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
         if let Some((x, y)) = points.next() {
-            return backend.draw_circle(
-                (x, y),
-                self.size,
-                &Box::new(self.style.color),
-                self.style.filled,
-            );
+            return backend.draw_circle((x, y), self.size, &self.style, self.style.filled);
         }
         Ok(())
     }
@@ -238,7 +277,8 @@ pub struct OwnedText<'a, Coord> {
 impl<'a, Coord> OwnedText<'a, Coord> {
     /// Create a new owned text element
     /// - `text`: The text to create
-    /// - `points`: The left upper conner
+    /// - `points`: The position of the text, which is the upper left corner unless the text
+    ///   style has another layout
     /// - `style`: The font style
     /// - Return the newly created owned text object
     pub fn new<S: Into<TextStyle<'a>>>(text: String, points: Coord, style: S) -> Self {
@@ -265,7 +305,13 @@ impl<'a, Coord: 'a> Drawable for OwnedText<'a, Coord> {
         backend: &mut DB,
     ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
         if let Some(a) = points.next() {
-            return backend.draw_text(&self.text, self.style.font, a, &Box::new(self.style.color));
+            return backend.draw_text(
+                &self.text,
+                self.style.font,
+                a,
+                &self.style.layout,
+                &Box::new(self.style.color),
+            );
         }
         Ok(())
     }
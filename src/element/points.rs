@@ -43,8 +43,8 @@ impl<'a, Coord: 'a> Drawable for Cross<'a, Coord> {
             let size = self.size as i32;
             let (x0, y0) = (x - size, y - size);
             let (x1, y1) = (x + size, y + size);
-            backend.draw_line((x0, y0), (x1, y1), &Box::new(self.style.color))?;
-            backend.draw_line((x0, y1), (x1, y0), &Box::new(self.style.color))?;
+            backend.draw_line((x0, y0), (x1, y1), &self.style)?;
+            backend.draw_line((x0, y1), (x1, y0), &self.style)?;
         }
         Ok(())
     }
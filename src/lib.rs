@@ -310,10 +310,13 @@ pub mod style;
 
 /// The module imports the most commonly used types and modules in Plotters
 pub mod prelude {
-    pub use crate::chart::{ChartBuilder, ChartContext};
+    pub use crate::chart::{
+        ChartBuilder, ChartContext, DualCoordChartContext, LegendGlyph, SeriesLabelPosition,
+    };
     pub use crate::coord::{
-        CoordTranslate, LogCoord, LogRange, LogScalable, Ranged, RangedCoord, RangedCoordf32,
-        RangedCoordf64, RangedCoordi32, RangedCoordi64, RangedCoordu32, RangedCoordu64, 
+        Camera, Category, CoordTranslate, LogCoord, LogRange, LogScalable, PolarCoord, Ranged,
+        RangedCategory, RangedCoord, RangedCoord3D, RangedCoordf32, RangedCoordf64, RangedCoordi32,
+        RangedCoordi64, RangedCoordu32, RangedCoordu64,
     };
     
     #[cfg(feature = "chrono")]
@@ -324,13 +327,13 @@ pub mod prelude {
     pub use crate::drawing::*;
     pub use crate::series::{Histogram, LineSeries, PointSeries};
     pub use crate::style::{
-        Black, Blue, Color, Cyan, FontDesc, Green, HSLColor, Magenta, Mixable, Palette, Palette100,
-        Palette99, Palette9999, PaletteColor, RGBColor, Red, ShapeStyle, SimpleColor, TextStyle,
-        Transparent, White, Yellow,
+        Black, Blue, Color, Cyan, FillRule, FontDesc, Green, HAnchor, HSLColor, LineCap, LineJoin,
+        Magenta, Mixable, Palette, Palette100, Palette99, Palette9999, PaletteColor, RGBColor, Red,
+        ShapeStyle, SimpleColor, TextLayout, TextStyle, Transparent, VAnchor, White, Yellow,
     };
 
     pub use crate::element::{
-        CandleStick, Circle, Cross, EmptyElement, OwnedText, Path, Pixel, Rectangle, Text,
+        CandleStick, Circle, Cross, EmptyElement, OwnedText, Path, Pixel, Polygon, Rectangle, Text,
     };
 
     #[allow(type_alias_bounds)]
@@ -47,6 +47,7 @@ where
     type Item = Rectangle<'a, (XR::ValueType, Y)>;
     fn next(&mut self) -> Option<Self::Item> {
         if let Some((x, y)) = self.iter.next() {
+            let x = XR::band_start(x);
             let nx = XR::next_value(&x);
             let mut rect = Rectangle::new([(x, y), (nx, Y::default())], self.style.clone());
             rect.set_margin(0, 0, self.x_margin, self.x_margin);
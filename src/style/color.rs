@@ -31,6 +31,16 @@ impl Color for Box<&dyn Color> {
     }
 }
 
+impl Color for &dyn Color {
+    fn rgb(&self) -> (u8, u8, u8) {
+        (**self).rgb()
+    }
+
+    fn alpha(&self) -> f64 {
+        (**self).alpha()
+    }
+}
+
 /// Color without alpha channel
 pub trait SimpleColor {
     fn rgb(&self) -> (u8, u8, u8);
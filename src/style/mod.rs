@@ -14,21 +14,189 @@ pub use color::{
 pub use font::{FontDesc, FontError, FontResult};
 pub use palette::*;
 
+/// Describes which point of the text is placed at the text position horizontally
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HAnchor {
+    /// The left side of the text
+    Left,
+    /// The horizontal center of the text
+    Center,
+    /// The right side of the text
+    Right,
+}
+
+/// Describes which point of the text is placed at the text position vertically
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VAnchor {
+    /// The top of the text
+    Top,
+    /// The vertical center of the text
+    Middle,
+    /// The baseline of the text
+    Baseline,
+    /// The bottom of the text
+    Bottom,
+}
+
+/// Describes how a text is placed relative to its position
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextLayout {
+    /// The horizontal anchor of the text
+    pub h_anchor: HAnchor,
+    /// The vertical anchor of the text
+    pub v_anchor: VAnchor,
+    /// The rotation of the text around the anchor point in degrees, counter-clockwise
+    pub rotation: f64,
+}
+
+impl Default for TextLayout {
+    fn default() -> Self {
+        Self {
+            h_anchor: HAnchor::Left,
+            v_anchor: VAnchor::Top,
+            rotation: 0.0,
+        }
+    }
+}
+
+impl TextLayout {
+    /// Create a text layout which places the given anchor point at the text position
+    /// - `h_anchor`: The horizontal anchor
+    /// - `v_anchor`: The vertical anchor
+    pub fn new(h_anchor: HAnchor, v_anchor: VAnchor) -> Self {
+        Self {
+            h_anchor,
+            v_anchor,
+            rotation: 0.0,
+        }
+    }
+
+    /// Make a text layout rotated around the anchor point
+    /// - `degrees`: The rotation in degrees, counter-clockwise
+    pub fn rotate(&self, degrees: f64) -> Self {
+        Self {
+            rotation: degrees,
+            ..*self
+        }
+    }
+
+    /// Get the anchor point of the text, relative to the position where the font draws the
+    /// unrotated text with `FontDesc::draw`
+    pub fn anchor_offset(&self, font: &FontDesc, text: &str) -> FontResult<(f64, f64)> {
+        Ok(self.anchor_offset_in(font.layout_box(text)?))
+    }
+
+    /// Get the anchor point of a text with the given layout box, see `anchor_offset`
+    pub(crate) fn anchor_offset_in(&self, layout_box: font::LayoutBox) -> (f64, f64) {
+        let ((min_x, min_y), (max_x, max_y)) = layout_box;
+        let x = match self.h_anchor {
+            HAnchor::Left => 0.0,
+            HAnchor::Center => f64::from(min_x + max_x) / 2.0,
+            HAnchor::Right => f64::from(max_x),
+        };
+        let y = match self.v_anchor {
+            VAnchor::Top => 0.0,
+            VAnchor::Middle => f64::from(max_y - min_y) / 2.0,
+            VAnchor::Baseline => f64::from(-min_y),
+            VAnchor::Bottom => f64::from(max_y - min_y),
+        };
+        (x, y)
+    }
+
+    /// Get the corners of the box of the text in clockwise order, starting from the upper left
+    /// corner of the unrotated text
+    /// - `pos`: The position of the anchor point
+    pub fn outline(
+        &self,
+        font: &FontDesc,
+        text: &str,
+        pos: (i32, i32),
+    ) -> FontResult<[(f64, f64); 4]> {
+        let ((min_x, min_y), (max_x, max_y)) = font.layout_box(text)?;
+        let (ax, ay) = self.anchor_offset(font, text)?;
+        let (left, right) = (f64::from(min_x) - ax, f64::from(max_x) - ax);
+        let (top, bottom) = (-ay, f64::from(max_y - min_y) - ay);
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+
+        let mut corners = [(left, top), (right, top), (right, bottom), (left, bottom)];
+        for corner in corners.iter_mut() {
+            let (u, v) = *corner;
+            *corner = (
+                f64::from(pos.0) + u * cos + v * sin,
+                f64::from(pos.1) + v * cos - u * sin,
+            );
+        }
+        Ok(corners)
+    }
+
+    /// Get the bounding box of the text, which contains the rotated text
+    /// - `pos`: The position of the anchor point
+    /// - Returns: The upper left and bottom right corner of the bounding box
+    pub fn bounding_box(
+        &self,
+        font: &FontDesc,
+        text: &str,
+        pos: (i32, i32),
+    ) -> FontResult<((i32, i32), (i32, i32))> {
+        let corners = self.outline(font, text, pos)?;
+        let (mut left, mut top, mut right, mut bottom) = (f64::MAX, f64::MAX, f64::MIN, f64::MIN);
+        for &(x, y) in corners.iter() {
+            left = left.min(x);
+            top = top.min(y);
+            right = right.max(x);
+            bottom = bottom.max(y);
+        }
+        Ok((
+            (left.floor() as i32, top.floor() as i32),
+            (right.ceil() as i32, bottom.ceil() as i32),
+        ))
+    }
+}
+
 /// Denotes an style of a text
 #[derive(Clone)]
 pub struct TextStyle<'a> {
     pub font: &'a FontDesc<'a>,
     pub color: &'a dyn Color,
+    /// How the text is placed relative to its position
+    pub layout: TextLayout,
 }
 
 impl<'a> TextStyle<'a> {
     /// Determine the color of the style
     pub fn color<C: Color>(&self, color: &'a C) -> Self {
         Self {
-            font: self.font,
             color,
+            ..self.clone()
         }
     }
+
+    /// Determine the layout of the style
+    /// - `layout`: How the text is placed relative to its position
+    pub fn layout(&self, layout: TextLayout) -> Self {
+        Self {
+            layout,
+            ..self.clone()
+        }
+    }
+
+    /// Make a text style which places the given anchor point at the text position
+    pub fn anchor(&self, h_anchor: HAnchor, v_anchor: VAnchor) -> Self {
+        self.layout(TextLayout {
+            h_anchor,
+            v_anchor,
+            ..self.layout
+        })
+    }
+
+    /// Make a text style rotated around the anchor point
+    /// - `degrees`: The rotation in degrees, counter-clockwise
+    pub fn rotate(&self, degrees: f64) -> Self {
+        self.layout(self.layout.rotate(degrees))
+    }
 }
 
 impl<'a, T: Borrow<FontDesc<'a>>> From<&'a T> for TextStyle<'a> {
@@ -36,23 +204,108 @@ impl<'a, T: Borrow<FontDesc<'a>>> From<&'a T> for TextStyle<'a> {
         Self {
             font: font.borrow(),
             color: &RGBColor(0, 0, 0),
+            layout: TextLayout::default(),
         }
     }
 }
 
+/// Describes how the end points of an open stroke are drawn
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineCap {
+    /// The stroke ends exactly at the end point
+    Butt,
+    /// The stroke ends with a half circle centered at the end point
+    Round,
+    /// The stroke is extended by half of the stroke width beyond the end point
+    Square,
+}
+
+/// Describes how two connected segments of a stroke are joined
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineJoin {
+    /// The outer edges are extended until they meet
+    Miter,
+    /// The corner is rounded with a circle
+    Round,
+    /// The corner is cut off by a straight line
+    Bevel,
+}
+
+/// Describes which part of a self-intersecting polygon is filled
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FillRule {
+    /// A point is inside if the polygon winds around it at least once
+    NonZero,
+    /// A point is inside if a ray from it crosses the polygon boundary odd times
+    EvenOdd,
+}
+
 /// Denotes an style for any of shape
 #[derive(Clone)]
 pub struct ShapeStyle<'a> {
     pub color: &'a dyn Color,
     pub filled: bool,
+    /// The stroke width in pixels
+    pub stroke_width: u32,
+    /// The dash pattern in pixels, alternating between drawn and skipped length.
+    /// An empty pattern means a solid stroke.
+    pub dash_pattern: &'a [u32],
+    pub line_cap: LineCap,
+    pub line_join: LineJoin,
+    pub fill_rule: FillRule,
 }
 
 impl<'a> ShapeStyle<'a> {
     /// Make a filled shape style
     pub fn filled(&self) -> Self {
         Self {
-            color: self.color,
             filled: true,
+            ..self.clone()
+        }
+    }
+
+    /// Make a shape style with the given stroke width
+    /// - `width`: The stroke width in pixels
+    pub fn stroke_width(&self, width: u32) -> Self {
+        Self {
+            stroke_width: width,
+            ..self.clone()
+        }
+    }
+
+    /// Make a dashed shape style
+    /// - `pattern`: The length of drawn and skipped parts in pixels, for example `&[5, 3]`
+    pub fn dashed(&self, pattern: &'a [u32]) -> Self {
+        Self {
+            dash_pattern: pattern,
+            ..self.clone()
+        }
+    }
+
+    /// Make a shape style with the given line cap
+    pub fn line_cap(&self, cap: LineCap) -> Self {
+        Self {
+            line_cap: cap,
+            ..self.clone()
+        }
+    }
+
+    /// Make a shape style with the given line join
+    pub fn line_join(&self, join: LineJoin) -> Self {
+        Self {
+            line_join: join,
+            ..self.clone()
+        }
+    }
+
+    /// Make a shape style with the given polygon fill rule
+    pub fn fill_rule(&self, rule: FillRule) -> Self {
+        Self {
+            fill_rule: rule,
+            ..self.clone()
         }
     }
 }
@@ -62,6 +315,11 @@ impl<'a, T: Color> From<&'a T> for ShapeStyle<'a> {
         ShapeStyle {
             color: f,
             filled: false,
+            stroke_width: 1,
+            dash_pattern: &[],
+            line_cap: LineCap::Butt,
+            line_join: LineJoin::Miter,
+            fill_rule: FillRule::NonZero,
         }
     }
 }